@@ -1,94 +1,54 @@
-use crate::{AOCOutput, Variant};
-use std::{convert::TryFrom, convert::TryInto, error::Error};
-
-/**
-# Part One
-
-Your flight departs in a few days from the coastal airport; the easiest way
-down to the coast from here is via toboggan.
-The shopkeeper at the North Pole Toboggan Rental Shop is having a bad day.
-"Something's wrong with our computers; we can't log in!" You ask if you can
-take a look.
- 
-Their password database seems to be a little corrupted: some of the
-passwords wouldn't have been allowed by the Official Toboggan Corporate
-Policy that was in effect when they were chosen.
- 
-To try to debug the problem, they have created a list (your puzzle input) of
-passwords (according to the corrupted database) and the corporate policy
-when that password was set.
- 
-For example, suppose you have the following list:
- 
-- 1-3 a: abcde 
-- 1-3 b: cdefg 
-- 2-9 c: ccccccccc 
-
-Each line gives the password
-policy and then the password. The password policy indicates the lowest and
-highest number of times a given letter must appear for the password to be
-valid. For example, 1-3 a means that the password must contain a at least 1
-time and at most 3 times.
- 
-In the above example, 2 passwords are valid. The middle password, cdefg, is
-not; it contains no instances of b, but needs at least 1. The first and
-third passwords are valid: they contain one a or nine c, both within the
-limits of their respective policies.
- 
-How many passwords are valid according to their policies?
- 
-# Part Two
-
-While it appears you validated the passwords correctly, they don't seem to
-be what the Official Toboggan Corporate Authentication System is expecting.
- 
-The shopkeeper suddenly realizes that he just accidentally explained the
-password policy rules from his old job at the sled rental place down the
-street! The Official Toboggan Corporate Policy actually works a little
-differently.
- 
-Each policy actually describes two positions in the password, where 1 means
-the first character, 2 means the second character, and so on. (Be careful;
-Toboggan Corporate Policies have no concept of "index zero"!) Exactly one of
-these positions must contain the given letter. Other occurrences of the
-letter are irrelevant for the purposes of policy enforcement.
- 
-Given the same example list from above:
- 
-- 1-3 a: abcde is valid: position 1 contains a and position 3 does not. 
-- 1-3 b: cdefg is invalid: neither position 1 nor position 3 contains b. 
-- 2-9 c: ccccccccc is invalid: both position 2 and position 9 contain c. 
-
-How many passwords are valid according to the new interpretation of the 
-policies?
-*/
-pub fn main(buffer: &String, variant: Variant) -> Result<AOCOutput, Box<dyn Error>> {
-    let count: u32 = buffer
-        .split('\n')
-        .into_iter()
-        
-        // map to password
-        .map(|str| str.trim().to_string().try_into().unwrap())
-       
-        // validate password
-        .map(|pw: Password| match variant {
-            Variant::One => pw.is_valid_for_sled_shop(),
-            Variant::Two => pw.is_valid_for_toboggan(),
+use aoc_core::{
+    error::{self, Error},
+    Input, Result, Solution,
+};
+use std::convert::TryFrom;
+
+#[doc = include_str!("../puzzles/2.md")]
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Password>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<Password>> {
+        Ok(input
+            .split('\n')
+            .map(|line| {
+                let line = line.trim();
+                Password::try_from(line).map_err(|error| error.within(input, line))
+            })
+            .collect::<std::result::Result<_, _>>()?)
+    }
+
+    fn part1(passwords: &Vec<Password>) -> Result<u32> {
+        count_valid(passwords, Password::is_valid_for_sled_shop)
+    }
+
+    fn part2(passwords: &Vec<Password>) -> Result<u32> {
+        count_valid(passwords, Password::is_valid_for_toboggan)
+    }
+}
+
+fn count_valid(passwords: &[Password], is_valid: fn(&Password) -> bool) -> Result<u32> {
+    let count: u32 = passwords
+        .iter()
+        .map(|password| {
+            let valid = is_valid(password);
+            aoc_core::trace!(
+                "{}-{} {}: {}", password.policy.min, password.policy.max,
+                password.policy.letter, password.password;
+                valid = valid
+            );
+            valid
         })
-      
         // map to 1 or 0 to allow for sum
-        .map(|bool| u32::from(bool))
+        .map(u32::from)
         .sum::<u32>();
 
-    let stderr = format!(
-        "{} out of {}",
-        count,
-        buffer.split("\n").collect::<Vec<&str>>().len()
-    );
+    aoc_core::info!("{} out of {}", count, passwords.len());
 
-    let stdout = format!("{}", count);
-
-    Ok(AOCOutput { stderr, stdout })
+    Ok(count)
 }
 
 struct PasswordPolicy {
@@ -97,45 +57,57 @@ struct PasswordPolicy {
     max: u8,
 }
 
-impl TryFrom<String> for PasswordPolicy {
-    type Error = Box<dyn Error>;
+impl TryFrom<&str> for PasswordPolicy {
+    type Error = Error;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let split: Vec<&str> = value
-            .split(|c| c == '-' || c == ' ')
-            .map(|s| s.trim())
-            .collect();
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let split: Vec<&str> = value.split(['-', ' ']).map(|s| s.trim()).collect();
 
-        if split.len() != 3 {
-            return Err(From::from("Password policy formatted incorrectly"));
-        }
+        let (min, max, letter) = match split[..] {
+            [min, max, letter] => (min, max, letter),
+            _ => return Err(Error::malformed("a policy like `1-3 a`").at(value, value)),
+        };
+
+        let mut letters = letter.chars();
+        let letter = match (letters.next(), letters.next()) {
+            (Some(only), None) => only,
+            _ => return Err(Error::malformed("a single letter").at(value, letter)),
+        };
 
-        let min: u8 = split.get(0).unwrap().parse()?;
-        let max: u8 = split.get(1).unwrap().parse()?;
-        let letter: char = split.get(2).unwrap().parse()?;
+        // Part two reads these as 1-based positions in the password.
+        let position = |number: &str| match error::number(value, number)? {
+            0 => Err(Error::malformed("a position counting from 1").at(value, number)),
+            position => Ok(position),
+        };
 
-        Ok(Self { min, max, letter })
+        Ok(Self {
+            min: position(min)?,
+            max: position(max)?,
+            letter,
+        })
     }
 }
 
-struct Password {
+pub struct Password {
     policy: PasswordPolicy,
     password: String,
 }
 
-impl TryFrom<String> for Password {
-    type Error = Box<dyn Error>;
-    fn try_from(string: String) -> Result<Self, Self::Error> {
-        let split: Vec<&str> = string.split(":").map(|s| s.trim()).collect();
+impl TryFrom<&str> for Password {
+    type Error = Error;
 
-        if split.len() != 2 {
-            return Err(From::from("Password entry formatted incorrectly"));
-        }
+    fn try_from(string: &str) -> std::result::Result<Self, Self::Error> {
+        let split: Vec<&str> = string.split(':').map(|s| s.trim()).collect();
+
+        let (policy, password) = match split[..] {
+            [policy, password] => (policy, password),
+            _ => return Err(Error::malformed("an entry like `1-3 a: abcde`").at(string, string)),
+        };
 
-        let policy = PasswordPolicy::try_from(split.get(0).unwrap().to_string())?;
         Ok(Self {
-            policy,
-            password: split.get(1).unwrap().to_string(),
+            policy: PasswordPolicy::try_from(policy)
+                .map_err(|error| error.within(string, policy))?,
+            password: password.to_string(),
         })
     }
 }
@@ -145,9 +117,8 @@ impl Password {
         let policy_letter_count = self
             .password
             .chars()
-            .into_iter()
             .map(|char| char == self.policy.letter)
-            .map(|bool| u8::from(bool))
+            .map(u8::from)
             .sum::<u8>();
 
         (self.policy.min..=self.policy.max).contains(&policy_letter_count)
@@ -165,4 +136,59 @@ impl Password {
             _ => false,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day2;
+    use aoc_core::{
+        examples::{assert_examples, Example},
+        Input, Solution,
+    };
+
+    const EXAMPLES: &[Example] = &[Example {
+        input: "\
+1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc",
+        part1: Some("2"),
+        part2: Some("1"),
+    }];
+
+    #[test]
+    fn examples() {
+        assert_examples::<Day2>(EXAMPLES);
+    }
+
+    #[test]
+    fn points_at_malformed_policies() {
+        let error = Day2::parse(&Input::new("1-3 a: abcde\n1-3b: cdefg"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected a policy like `1-3 a`"
+        );
+    }
+
+    #[test]
+    fn points_at_positions_below_one() {
+        let error = Day2::parse(&Input::new("1-3 a: abcde\n0-3 a: abc"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected a position counting from 1"
+        );
+    }
+
+    #[test]
+    fn it_works() {
+        crate::assert_answers(2);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(2);
+    }
+}
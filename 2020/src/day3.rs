@@ -1,164 +1,106 @@
-use crate::{AOCOutput, Variant};
-use std::error::Error;
-
-
-/**
-# Part One
+use aoc_core::{
+    error::Error,
+    visual::{self, Frame, View},
+    Input, Result, Solution,
+};
+
+/// How many rows of the map each frame of the descent shows.
+const WINDOW: usize = 11;
+
+#[doc = include_str!("../puzzles/3.md")]
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Vec<Vec<char>>;
+    type Answer = usize;
+
+    fn parse(input: &Input) -> Result<Vec<Vec<char>>> {
+        let mut width = None;
+        let rows = input
+            .split('\n')
+            .map(|line| {
+                if line.is_empty() {
+                    return Err(Error::malformed("a row of `.` and `#`").at(input, line));
+                }
+                if let Some((index, square)) = line
+                    .char_indices()
+                    .find(|&(_, square)| square != '.' && square != '#')
+                {
+                    let square = &line[index..index + square.len_utf8()];
+                    return Err(
+                        Error::malformed("`.` for open ground or `#` for a tree").at(input, square)
+                    );
+                }
+                let width = *width.get_or_insert(line.len());
+                if line.len() != width {
+                    return Err(Error::malformed(format!(
+                        "a row {} squares wide, like the first",
+                        width
+                    ))
+                    .at(input, line));
+                }
+                Ok(line.chars().collect())
+            })
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(rows)
+    }
 
-With the toboggan login problems resolved, you set off toward the airport.
-While travel by toboggan might be easy, it's certainly not safe: there's
-very minimal steering and the area is covered in trees. You'll need to see
-which angles will take you near the fewest trees.
+    fn part1(lines: &Vec<Vec<char>>) -> Result<usize> {
+        let tree_count = get_tree_count_with_slope(lines, &Slope { x: 3, y: 1 });
+        aoc_core::info!("{} trees in {} lines", tree_count, lines.len());
+        Ok(tree_count)
+    }
 
-Due to the local geology, trees in this area only grow on exact integer
-coordinates in a grid. You make a map (your puzzle input) of the open
-squares (.) and trees (#) you can see. For example:
+    fn part2(lines: &Vec<Vec<char>>) -> Result<usize> {
+        let slopes = vec![
+            Slope { x: 1, y: 1 },
+            Slope { x: 3, y: 1 },
+            Slope { x: 5, y: 1 },
+            Slope { x: 7, y: 1 },
+            Slope { x: 1, y: 2 },
+        ];
 
-```
-..##.......
-#...#...#..
-.#....#..#.
-..#.#...#.#
-.#...##..#.
-..#.##.....
-.#.#.#....#
-.#........#
-#.##...#...
-#...##....#
-.#..#...#.#
-```
-
-These aren't the only trees, though; due to something you read about once
-involving arboreal genetics and biome stability, the same pattern repeats to
-the right many times:
-
-```
-..##.........##.........##.........##.........##.........##.......  --->
-#...#...#..#...#...#..#...#...#..#...#...#..#...#...#..#...#...#..
-.#....#..#..#....#..#..#....#..#..#....#..#..#....#..#..#....#..#.
-..#.#...#.#..#.#...#.#..#.#...#.#..#.#...#.#..#.#...#.#..#.#...#.#
-.#...##..#..#...##..#..#...##..#..#...##..#..#...##..#..#...##..#.
-..#.##.......#.##.......#.##.......#.##.......#.##.......#.##.....  --->
-.#.#.#....#.#.#.#....#.#.#.#....#.#.#.#....#.#.#.#....#.#.#.#....#
-.#........#.#........#.#........#.#........#.#........#.#........#
-#.##...#...#.##...#...#.##...#...#.##...#...#.##...#...#.##...#...
-#...##....##...##....##...##....##...##....##...##....##...##....#
-.#..#...#.#.#..#...#.#.#..#...#.#.#..#...#.#.#..#...#.#.#..#...#.#  --->
-```
-
-You start on the open square (.) in the top-left corner and need to reach the
-bottom (below the bottom-most row on your map).
-
-The toboggan can only follow a few specific slopes (you opted for a cheaper
-model that prefers rational numbers); start by counting all the trees you
-would encounter for the slope right 3, down 1:
-
-From your starting position at the top-left, check the position that is right
-3 and down 1. Then, check the position that is right 3 and down 1 from there,
-and so on until you go past the bottom of the map.
-
-The locations you'd check in the above example are marked here with O where
-there was an open square and X where there was a tree:
-
-```
-..##.........##.........##.........##.........##.........##.......  --->
-#..O#...#..#...#...#..#...#...#..#...#...#..#...#...#..#...#...#..
-.#....X..#..#....#..#..#....#..#..#....#..#..#....#..#..#....#..#.
-..#.#...#O#..#.#...#.#..#.#...#.#..#.#...#.#..#.#...#.#..#.#...#.#
-.#...##..#..X...##..#..#...##..#..#...##..#..#...##..#..#...##..#.
-..#.##.......#.X#.......#.##.......#.##.......#.##.......#.##.....  --->
-.#.#.#....#.#.#.#.O..#.#.#.#....#.#.#.#....#.#.#.#....#.#.#.#....#
-.#........#.#........X.#........#.#........#.#........#.#........#
-#.##...#...#.##...#...#.X#...#...#.##...#...#.##...#...#.##...#...
-#...##....##...##....##...#X....##...##....##...##....##...##....#
-.#..#...#.#.#..#...#.#.#..#...X.#.#..#...#.#.#..#...#.#.#..#...#.#  --->
-```
-
-In this example, traversing the map using this slope would cause you to
-encounter 7 trees.
-
-Starting at the top-left corner of your map and following a slope of right 3
-and down 1, how many trees would you encounter?
-
-# Part Two
-
-Time to check the rest of the slopes - you need to minimize the probability
-of a sudden arboreal stop, after all.
-
-Determine the number of trees you would encounter if, for each of the
-following slopes, you start at the top-left corner and traverse the map all
-the way to the bottom:
-
-- Right 1, down 1.
-- Right 3, down 1. (This is the slope you already checked.)
-- Right 5, down 1.
-- Right 7, down 1.
-- Right 1, down 2.
-
-In the above example, these slopes would find 2, 7, 3, 4, and 2 tree(s)
-respectively; multiplied together, these produce the answer 336.
-
-What do you get if you multiply together the number of trees encountered on
-each of the listed slopes?
-*/
-pub fn main(buffer: &String, variant: Variant) -> Result<AOCOutput, Box<dyn Error>> {
-    let lines: Vec<&str> = buffer.split('\n').collect();
-    let lines_count = &lines.clone().len();
-
-    fn get_tree_count_with_slope(lines: &Vec<&str>, slope: &Slope) -> usize {
-        let line_length = lines.first().unwrap().len();
-        lines
+        let tree_counts: Vec<usize> = slopes
             .into_iter()
-            .enumerate()
-            .step_by(slope.y)
-            .map(|(y_position, line)| {
-                let x_index =
-                    (y_position as f64 * (slope.x as f64 / slope.y as f64)) as usize % line_length;
-                match line.chars().collect::<Vec<char>>().get(x_index) {
-                    Some('#') => 1,
-                    _ => 0,
-                }
+            .map(|slope| {
+                let tree_count = get_tree_count_with_slope(lines, &slope);
+                aoc_core::info!(
+                    "Right {}, Down {}, Trees {}",
+                    &slope.x,
+                    &slope.y,
+                    tree_count
+                );
+                tree_count
             })
-            .sum()
+            .collect();
+
+        let product = tree_counts.iter().product();
+        aoc_core::info!(
+            "{} = {}",
+            tree_counts
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(" × "),
+            product
+        );
+
+        Ok(product)
     }
+}
 
-    match variant {
-        Variant::One => {
-            let tree_count = get_tree_count_with_slope(&lines, &Slope { x: 3, y: 1 });
-            let stderr = format!("{} trees in {} lines", tree_count, lines_count);
-            let stdout = format!("{}", tree_count);
-            Ok(AOCOutput { stderr, stdout })
-        }
-
-        Variant::Two => {
-            let slopes = vec![
-                Slope { x: 1, y: 1 },
-                Slope { x: 3, y: 1 },
-                Slope { x: 5, y: 1 },
-                Slope { x: 7, y: 1 },
-                Slope { x: 1, y: 2 },
-            ];
-
-            let mut stderr_components = vec![];
-
-            let product: usize = slopes
-                .into_iter()
-                .map(|slope| {
-                    let tree_count = get_tree_count_with_slope(&lines, &slope);
-                    stderr_components.push(format!(
-                        "Right {}, Down {}, Trees {}",
-                        &slope.x, &slope.y, tree_count
-                    ));
-                    tree_count
-                })
-                .product();
-
-            let stderr = format!("{}\n{}", stderr_components.join("\n"), product);
-            let stdout = format!("{}", product);
-
-            Ok(AOCOutput { stderr, stdout })
-        }
+/// Counts the trees on the way down. `parse` has made sure every row is the
+/// same, non-zero width.
+fn get_tree_count_with_slope(lines: &[Vec<char>], slope: &Slope) -> usize {
+    let mut tree_count = 0;
+    for (y_position, line) in lines.iter().enumerate().step_by(slope.y) {
+        let x_index = slope.column(y_position, line.len());
+        let tree = line.get(x_index) == Some(&'#');
+        aoc_core::trace!("step"; x = x_index, y = y_position, tree = tree);
+        tree_count += usize::from(tree);
+        visual::frame(|| descent(lines, slope, y_position, tree_count));
     }
+    tree_count
 }
 
 struct Slope {
@@ -166,7 +108,49 @@ struct Slope {
     y: usize,
 }
 
-#[allow(dead_code)]
+impl Slope {
+    /// Where the toboggan is across a map `line_length` wide when it reaches
+    /// row `y_position`.
+    fn column(&self, y_position: usize, line_length: usize) -> usize {
+        (y_position as f64 * (self.x as f64 / self.y as f64)) as usize % line_length
+    }
+}
+
+/// The rows of the map around `y_position`, with every square the toboggan
+/// has passed through so far marked `O`, or `X` where it hit a tree.
+fn descent(lines: &[Vec<char>], slope: &Slope, y_position: usize, tree_count: usize) -> Frame {
+    let top = y_position.saturating_sub(WINDOW / 2);
+    let rows = lines
+        .iter()
+        .enumerate()
+        .skip(top)
+        .take(WINDOW)
+        .map(|(row, line)| {
+            let line: String = line.iter().collect();
+            if row > y_position || row % slope.y != 0 {
+                return line;
+            }
+            let x_index = slope.column(row, line.len());
+            match line.chars().nth(x_index) {
+                Some('#') => replace_nth_letter_in_string(&line, x_index, 'X'),
+                _ => replace_nth_letter_in_string(&line, x_index, 'O'),
+            }
+        })
+        .collect();
+
+    Frame {
+        caption: format!(
+            "Right {}, Down {}: row {} of {}, {} trees so far",
+            slope.x,
+            slope.y,
+            y_position + 1,
+            lines.len(),
+            tree_count
+        ),
+        view: View::Grid(rows),
+    }
+}
+
 fn replace_nth_letter_in_string(original: &str, index: usize, replacement: char) -> String {
     original
         .chars()
@@ -179,4 +163,60 @@ fn replace_nth_letter_in_string(original: &str, index: usize, replacement: char)
             }
         })
         .collect::<String>()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day3;
+    use aoc_core::{
+        examples::{assert_examples, Example},
+        Input, Solution,
+    };
+
+    const EXAMPLES: &[Example] = &[Example {
+        input: "\
+..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#",
+        part1: Some("7"),
+        part2: Some("336"),
+    }];
+
+    #[test]
+    fn examples() {
+        assert_examples::<Day3>(EXAMPLES);
+    }
+
+    #[test]
+    fn rejects_malformed_maps() {
+        let error = |input: &str| Day3::parse(&Input::new(input)).err().unwrap().to_string();
+
+        assert_eq!(error(""), "line 1, column 1: expected a row of `.` and `#`");
+        assert_eq!(
+            error("..#\n.#\n#.."),
+            "line 2, column 1: expected a row 3 squares wide, like the first"
+        );
+        assert_eq!(
+            error("..#\n.O.\n#.."),
+            "line 2, column 2: expected `.` for open ground or `#` for a tree"
+        );
+    }
+
+    #[test]
+    fn it_works() {
+        crate::assert_answers(3);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(3);
+    }
+}
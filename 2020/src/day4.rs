@@ -1,138 +1,58 @@
-use crate::{AOCOutput, Variant};
-use std::{collections::HashMap, convert::TryFrom, error::Error};
+use aoc_core::{error::Error, Input, Result, Solution};
+use std::collections::HashMap;
 
-/**
+#[doc = include_str!("../puzzles/4.md")]
+pub struct Day4;
 
-# Part One
+impl Solution for Day4 {
+    /// One entry per passport in the batch file, `None` where a required
+    /// field is missing.
+    type Parsed = Vec<Option<Passport>>;
+    type Answer = usize;
 
-You arrive at the airport only to realize that you grabbed your North Pole
-Credentials instead of your passport. While these documents are extremely
-similar, North Pole Credentials aren't issued by a country and therefore
-aren't actually valid documentation for travel in most of the world.
-
-It seems like you're not the only one having problems, though; a very long
-line has formed for the automatic passport scanners, and the delay could
-upset your travel itinerary.
-
-Due to some questionable network security, you realize you might be able to
-solve both of these problems at the same time.
-
-The automatic passport scanners are slow because they're having trouble
-detecting which passports have all required fields. The expected fields are
-as follows:
-
-- byr (Birth Year)
-- iyr (Issue Year)
-- eyr (Expiration Year)
-- hgt (Height)
-- hcl (Hair Color)
-- ecl (Eye Color)
-- pid (Passport ID)
-- cid (Country ID)
-
-Passport data is validated in batch files (your puzzle input). Each passport
-is represented as a sequence of key:value pairs separated by spaces or
-newlines. Passports are separated by blank lines.
-
-Here is an example batch file containing four passports:
-
-```
-ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
-byr:1937 iyr:2017 cid:147 hgt:183cm
+    fn parse(input: &Input) -> Result<Vec<Option<Passport>>> {
+        Ok(input
+            .split("\n\n")
+            .map(|entry| {
+                fields(entry)
+                    .map(|fields| Passport::from_fields(&fields))
+                    .map_err(|error| error.within(input, entry))
+            })
+            .collect::<std::result::Result<_, _>>()?)
+    }
 
-iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
-hcl:#cfa07d byr:1929
+    fn part1(passports: &Vec<Option<Passport>>) -> Result<usize> {
+        let passport_count_with_fields_present = passports
+            .iter()
+            .filter(|passport| passport.is_some())
+            .count();
 
-hcl:#ae17e1 iyr:2013
-eyr:2024
-ecl:brn pid:760753108 byr:1931
-hgt:179cm
+        Ok(count_output(passport_count_with_fields_present, passports))
+    }
 
-hcl:#cfa07d eyr:2025 pid:166559648
-iyr:2011 ecl:brn hgt:59in
-```
-
-The first passport is valid - all eight fields are present. The second
-passport is invalid - it is missing hgt (the Height field).
-
-The third passport is interesting; the only missing field is cid, so it looks
-like data from North Pole Credentials, not a passport at all! Surely, nobody
-would mind if you made the system temporarily ignore missing cid fields.
-Treat this "passport" as valid.
-
-The fourth passport is missing two fields, cid and byr. Missing cid is fine,
-but missing any other field is not, so this passport is invalid.
-
-According to the above rules, your improved system would report 2 valid
-passports.
-
-Count the number of valid passports - those that have all required fields.
-Treat cid as optional. In your batch file, how many passports are valid?
-
-# Part Two
-
-The line is moving more quickly now, but you overhear airport security
-talking about how passports with invalid data are getting through. Better add
-some data validation, quick!
-
-You can continue to ignore the cid field, but each other field has strict
-rules about what values are valid for automatic validation:
-
-- byr (Birth Year) - four digits; at least 1920 and at most 2002.
-- iyr (Issue Year) - four digits; at least 2010 and at most 2020.
-- eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
-- hgt (Height) - a number followed by either cm or in:
-    - If cm, the number must be at least 150 and at most 193.
-    - If in, the number must be at least 59 and at most 76.
-- hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
-- ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
-- pid (Passport ID) - a nine-digit number, including leading zeroes.
-- cid (Country ID) - ignored, missing or not.
-
-Your job is to count the passports where all required fields are both present
-and valid according to the above rules. Here are some example values:
-
-*/
-pub fn main(buffer: &String, variant: Variant) -> Result<AOCOutput, Box<dyn Error>> {
-    let passport_count_with_fields_present: usize = buffer
-        .split("\n\n")
-        .map(Passport::try_from)
-        .map(|result: Result<Passport, Box<dyn Error>>| result.is_ok())
-        .fold(0, |acc, x| acc + (if x { 1 } else { 0 }));
-
-    let passport_count_with_valid_values: usize = buffer
-        .split("\n\n")
-        .map(Passport::try_from)
-        .map(|result| match result {
-            Err(_e) => 0,
-            Ok(passport) => {
-                if passport.is_valid() {
-                    1
-                } else {
-                    0
-                }
-            }
-        })
-        .sum();
+    fn part2(passports: &Vec<Option<Passport>>) -> Result<usize> {
+        let passport_count_with_valid_values = passports
+            .iter()
+            .flatten()
+            .filter(|passport| passport.is_valid())
+            .count();
 
-    let valid_passport_count = match variant {
-        Variant::One => passport_count_with_fields_present,
-        Variant::Two => passport_count_with_valid_values,
-    };
+        Ok(count_output(passport_count_with_valid_values, passports))
+    }
+}
 
-    Ok(AOCOutput {
-        stderr: format!(
-            "{} out of {} passports",
-            valid_passport_count,
-            buffer.split("\n\n").collect::<Vec<&str>>().len()
-        ),
+fn count_output(valid_passport_count: usize, passports: &[Option<Passport>]) -> usize {
+    aoc_core::info!(
+        "{} out of {} passports",
+        valid_passport_count,
+        passports.len()
+    );
 
-        stdout: format!("{}", valid_passport_count),
-    })
+    valid_passport_count
 }
 
 #[derive(Debug)]
-struct Passport {
+pub struct Passport {
     byr: String,
     iyr: String,
     eyr: String,
@@ -143,8 +63,21 @@ struct Passport {
 }
 
 impl Passport {
+    /// `None` when a required field is missing; `cid` is optional.
+    fn from_fields(fields: &HashMap<&str, &str>) -> Option<Passport> {
+        Some(Passport {
+            byr: fields.get("byr")?.to_string(),
+            iyr: fields.get("iyr")?.to_string(),
+            eyr: fields.get("eyr")?.to_string(),
+            hgt: fields.get("hgt")?.to_string(),
+            hcl: fields.get("hcl")?.to_string(),
+            ecl: fields.get("ecl")?.to_string(),
+            pid: fields.get("pid")?.to_string(),
+        })
+    }
+
     fn is_valid(&self) -> bool {
-        fn length_and_within_range(string: &String, len: usize, min: u32, max: u32) -> bool {
+        fn length_and_within_range(string: &str, len: usize, min: u32, max: u32) -> bool {
             let r = string.parse::<u32>();
             let string_len = string.chars().collect::<Vec<char>>().len();
             match r {
@@ -152,21 +85,21 @@ impl Passport {
                 Ok(num) => (min..=max).contains(&num) && len == string_len,
             }
         }
-        fn byr_valid(byr: &String) -> bool {
+        fn byr_valid(byr: &str) -> bool {
             length_and_within_range(byr, 4, 1920, 2002)
         }
 
-        fn iyr_valid(iyr: &String) -> bool {
+        fn iyr_valid(iyr: &str) -> bool {
             length_and_within_range(iyr, 4, 2010, 2020)
         }
 
-        fn eyr_valid(eyr: &String) -> bool {
+        fn eyr_valid(eyr: &str) -> bool {
             length_and_within_range(eyr, 4, 2020, 2030)
         }
 
-        fn hgt_valid(hgt: &String) -> bool {
+        fn hgt_valid(hgt: &str) -> bool {
             let unit = hgt.chars().rev().take(2).collect::<String>();
-            let mut number = hgt.clone();
+            let mut number = hgt.to_owned();
             number.pop();
             number.pop();
 
@@ -177,28 +110,26 @@ impl Passport {
             }
         }
 
-        fn hcl_valid(hcl: &String) -> bool {
+        fn hcl_valid(hcl: &str) -> bool {
             let vec: Vec<char> = hcl.chars().collect();
             let (octothorpe, hex_string) = vec.split_at(1);
-            let valid_hex_string = hex_string
-                .into_iter()
-                .fold(true, |acc, char| acc && char.is_ascii_hexdigit());
+            let valid_hex_string = hex_string.iter().all(|char| char.is_ascii_hexdigit());
             octothorpe[0] == '#' && valid_hex_string
         }
 
-        fn ecl_valid(ecl: &String) -> bool {
-            let valids = vec!["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
-            valids.contains(&ecl.as_str())
+        fn ecl_valid(ecl: &str) -> bool {
+            let valids = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+            valids.contains(&ecl)
         }
 
-        fn pid_valid(pid: &String) -> bool {
+        fn pid_valid(pid: &str) -> bool {
             let string_length = pid.chars().collect::<Vec<char>>().len();
             let all_numeric = pid.chars().fold(true, |acc, char| acc & char.is_numeric());
             string_length == 9 && all_numeric
         }
 
         let validations: Vec<(&str, &String, bool)> = vec![
-            ( "byr", &self.byr, byr_valid(&self.byr)),
+            ("byr", &self.byr, byr_valid(&self.byr)),
             ("iyr", &self.iyr, iyr_valid(&self.iyr)),
             ("eyr", &self.eyr, eyr_valid(&self.eyr)),
             ("hgt", &self.hgt, hgt_valid(&self.hgt)),
@@ -207,41 +138,117 @@ impl Passport {
             ("pid", &self.pid, pid_valid(&self.pid)),
         ];
 
-        validations.into_iter().fold(true, |acc, v| acc && v.2)
+        for (field, value, valid) in &validations {
+            if !valid {
+                aoc_core::debug!("invalid {}", field; value = value, pid = self.pid);
+            }
+        }
+
+        validations.into_iter().all(|v| v.2)
     }
 }
 
-impl TryFrom<&str> for Passport {
-    type Error = Box<dyn Error>;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut passport_entries = HashMap::new();
-        value
-            .split(|char| char == ' ' || char == '\n')
-            .map(|entry| {
-                let vec: Vec<&str> = entry.split(":").collect();
-                (vec.get(0).unwrap().clone(), vec.get(1).unwrap().clone())
-            })
-            .for_each(|(k, v)| {
-                passport_entries.insert(k, v);
-            });
-
-        let byr = passport_entries.get("byr").ok_or("No byr")?.to_string();
-        let iyr = passport_entries.get("iyr").ok_or("No iyr")?.to_string();
-        let eyr = passport_entries.get("eyr").ok_or("No eyr")?.to_string();
-        let hgt = passport_entries.get("hgt").ok_or("No hgt")?.to_string();
-        let hcl = passport_entries.get("hcl").ok_or("No hcl")?.to_string();
-        let ecl = passport_entries.get("ecl").ok_or("No ecl")?.to_string();
-        let pid = passport_entries.get("pid").ok_or("No pid")?.to_string();
-
-        Ok(Passport {
-            byr,
-            iyr,
-            eyr,
-            hgt,
-            hcl,
-            ecl,
-            pid,
+/// The `key:value` fields of one passport's entry.
+fn fields(entry: &str) -> std::result::Result<HashMap<&str, &str>, Error> {
+    entry
+        .split_whitespace()
+        .map(|field| {
+            field
+                .split_once(':')
+                .ok_or_else(|| Error::malformed("a field like `byr:1937`").at(entry, field))
         })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day4;
+    use aoc_core::{
+        examples::{assert_examples, Example},
+        Input, Solution,
+    };
+
+    const EXAMPLES: &[Example] = &[
+        Example {
+            input: "\
+ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in",
+            part1: Some("2"),
+            part2: None,
+        },
+        // The description above stops before part two's examples; these are
+        // its invalid and valid passports respectively.
+        Example {
+            input: "\
+eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007",
+            part1: Some("4"),
+            part2: Some("0"),
+        },
+        Example {
+            input: "\
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
+            part1: Some("4"),
+            part2: Some("4"),
+        },
+    ];
+
+    #[test]
+    fn examples() {
+        assert_examples::<Day4>(EXAMPLES);
+    }
+
+    #[test]
+    fn points_at_malformed_fields() {
+        let error = Day4::parse(&Input::new("byr:1937\n\niyr:2017 cid147"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 10: expected a field like `byr:1937`"
+        );
+    }
+
+    #[test]
+    fn it_works() {
+        crate::assert_answers(4);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(4);
     }
 }
@@ -0,0 +1,97 @@
+use aoc_core::{
+    answers::Answers,
+    bench::{self, Measurement},
+    input,
+    runner::{self, Record},
+    sandbox::{self, Limits},
+    Entry, Part, Result,
+};
+use std::path::{Path, PathBuf};
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+
+pub const YEAR: u16 = 2020;
+
+/// Where each day's input lives, regardless of the working directory.
+pub const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/inputs");
+
+/// Where each day's puzzle description lives, as `N.md`.
+pub const PUZZLE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/puzzles");
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Day1>(YEAR, 1),
+        Entry::new::<day2::Day2>(YEAR, 2),
+        Entry::new::<day3::Day3>(YEAR, 3),
+        Entry::new::<day4::Day4>(YEAR, 4),
+    ]
+}
+
+pub fn input_path(day: u8) -> PathBuf {
+    input::default_path(INPUT_DIR, day)
+}
+
+pub fn input(day: u8) -> Result<String> {
+    input::read(&input_path(day))
+}
+
+/// Accepted answers, shared by the tests and `aoc verify`.
+pub const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+pub fn answers() -> Result<Answers> {
+    Answers::load(Path::new(ANSWERS_PATH))
+}
+
+#[cfg(test)]
+fn assert_answers(day: u8) {
+    use aoc_core::answers::{assert_day, assert_solution};
+    assert_solution(assert_day, solutions(), input, answers(), day);
+}
+
+#[cfg(test)]
+fn assert_variants(day: u8) {
+    use aoc_core::answers::{assert_solution, assert_variants};
+    assert_solution(assert_variants, solutions(), input, answers(), day);
+}
+
+fn parts(variant: &str) -> Result<Vec<Part>> {
+    match variant {
+        "1" => Ok(vec![Part::One]),
+        "2" => Ok(vec![Part::Two]),
+        "both" => Ok(vec![Part::One, Part::Two]),
+        _ => Err(From::from(format!("Variant `{}` not recognized", variant))),
+    }
+}
+
+/// Runs the requested variant ("1", "2" or "both") of a day against the
+/// input at `path` (`-` for stdin), parsing it once for however many parts
+/// are run. With `limits`, the day runs in a sandboxed child process.
+pub fn execute_puzzle(
+    path: &Path,
+    entry: &Entry,
+    variant: &str,
+    limits: Option<&Limits>,
+) -> Result<Vec<Record>> {
+    let parts = parts(variant)?;
+
+    Ok(match (input::read(path), limits) {
+        (Ok(buffer), Some(limits)) => sandbox::run(entry, &buffer, &parts, limits),
+        (Ok(buffer), None) => runner::run(entry, &buffer, &parts),
+        (Err(error), _) => runner::failed(entry, &parts, &error),
+    })
+}
+
+/// Like `execute_puzzle`, but samples parse and solve times many times over,
+/// recording them in the benchmark history.
+pub fn bench_puzzle(
+    path: &Path,
+    entry: &Entry,
+    variant: &str,
+    options: &bench::Options,
+) -> Result<Vec<Measurement>> {
+    let parts = parts(variant)?;
+    bench::bench_and_record(entry, &input::read(path)?, &parts, options)
+}
@@ -1,64 +1,191 @@
+use aoc_2020::{bench_puzzle, execute_puzzle, input_path, solutions};
+use aoc_core::{
+    bench, cli,
+    report::{Format, FORMATS},
+    runner::{self, Record, Status},
+    sandbox::{self, Limits},
+    visual::{self, Playback, Screen},
+    Entry,
+};
 use clap::{App, Arg};
 use std::{
-    error::Error,
-    fs::File,
-    io::{BufReader, Read},
+    path::{Path, PathBuf},
     process::exit,
 };
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-
-pub struct AOCOutput {
-    stderr: String,
-    stdout: String,
-}
-
-pub enum Variant {
-    One,
-    Two,
-}
-
-pub type OperationOutput = Result<AOCOutput, Box<dyn Error>>;
-pub type Operation = fn(&String, Variant) -> OperationOutput;
-
 fn main() {
+    sandbox::serve(&solutions());
+
     let matches = App::new("Advent of Code")
         .version("2020")
         .author("James Little")
         .about("https://adventofcode.com")
-        .arg(Arg::new("day").short('d').required(true).takes_value(true))
+        .arg(
+            Arg::new("day")
+                .short('d')
+                .required_unless_present("all")
+                .conflicts_with("all")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("all")
+                .long("all")
+                .help("Run every day and print a summary table"),
+        )
         .arg(
             Arg::new("variant")
                 .short('v')
+                .short_alias('p')
+                .long("part")
+                .alias("variant")
                 .takes_value(true)
                 .default_value("2")
-                .possible_values(&["1", "2"]),
+                .possible_values(["1", "2", "both"]),
+        )
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .takes_value(true)
+                .requires("day")
+                .help("Read the input from this path instead, or from stdin with `-`"),
         )
+        .args(cli::trace_args(false))
+        .args(cli::visual_args())
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .takes_value(true)
+                .default_value("human")
+                .possible_values(FORMATS),
+        )
+        .args(cli::bench_args())
+        .args(cli::sandbox_args())
         .get_matches();
 
     let variant = matches.value_of("variant").unwrap();
-    let day = matches.value_of("day").unwrap();
-    let operation_result: OperationOutput = match day {
-        "1" => execute_puzzle("./inputs/1.txt", day1::main, variant),
-        "2" => execute_puzzle("./inputs/2.txt", day2::main, variant),
-        "3" => execute_puzzle("./inputs/3.txt", day3::main, variant),
-        "4" => execute_puzzle("./inputs/4.txt", day4::main, variant),
-        _ => {
-            eprintln!("Day `{}` not recognized", day);
-            exit(1);
-        }
+    let format: Format = matches.value_of_t_or_exit("format");
+    let limits = cli::limits(&matches);
+    cli::set_trace_level(&matches);
+    let playback = cli::playback(&matches);
+    let entries = match matches.value_of("day") {
+        None => solutions(),
+        Some(day) => match solutions()
+            .into_iter()
+            .find(|entry| entry.day.to_string() == day)
+        {
+            Some(entry) => vec![entry],
+            None => {
+                eprintln!("Day `{}` not recognized", day);
+                exit(1);
+            }
+        },
     };
 
+    let input = matches.value_of("input").map(PathBuf::from);
+    let input_path = |entry: &Entry| input.clone().unwrap_or_else(|| input_path(entry.day));
+
+    if let Some(options) = cli::bench_options(&matches) {
+        let mut failed = false;
+        bench::print_header(format);
+        for entry in &entries {
+            match bench_puzzle(&input_path(entry), entry, variant, &options) {
+                Ok(measurements) => bench::print(format, &measurements),
+                Err(error) => {
+                    failed = true;
+                    eprintln!("Day {}: {}", entry.day, error);
+                }
+            }
+        }
+        exit(if failed { 1 } else { 0 });
+    }
+
+    if entries.len() == 1 && variant != "both" && format == Format::Human {
+        run_single(
+            &input_path(&entries[0]),
+            &entries[0],
+            variant,
+            limits.as_ref(),
+            playback,
+        );
+    }
+
+    let mut failed = false;
+    match (format, format.header::<Record>()) {
+        (Format::Human, _) => println!("{:>3}  {:>4}  Answer", "Day", "Part"),
+        (_, Some(header)) => println!("{}", header),
+        (_, None) => {}
+    }
+
+    for entry in &entries {
+        let records = match execute_puzzle(&input_path(entry), entry, variant, limits.as_ref()) {
+            Ok(records) => records,
+            Err(error) => {
+                eprintln!("{}", error);
+                exit(1);
+            }
+        };
+
+        for record in &records {
+            if let Some(playback) = playback {
+                visual::play(&record.frames, playback, format.screen());
+            }
+            failed |= record.status != Status::Ok;
+            match format.line(record) {
+                Some(line) => println!("{}", line),
+                None => print_row(record),
+            }
+        }
+        runner::warn(&records);
+    }
+
+    exit(if failed { 1 } else { 0 })
+}
+
+fn run_single(
+    path: &Path,
+    entry: &Entry,
+    variant: &str,
+    limits: Option<&Limits>,
+    playback: Option<Playback>,
+) -> ! {
+    let operation_result = execute_puzzle(path, entry, variant, limits).map(|mut records| {
+        if let Some(playback) = playback {
+            visual::play(&records[0].frames, playback, Screen::Stdout);
+        }
+        runner::warn(&records);
+        records.remove(0)
+    });
+
     match operation_result {
-        Ok(output) => {
-            eprintln!("{}", output.stderr);
-            println!("{}", output.stdout);
+        Ok(Record {
+            answer: Some(answer),
+            events,
+            ..
+        }) => {
+            for event in events {
+                eprintln!("{}", event);
+            }
+            println!("{}", answer);
             exit(0)
         }
 
+        Ok(Record {
+            error,
+            diagnostic,
+            events,
+            ..
+        }) => {
+            for event in events {
+                eprintln!("{}", event);
+            }
+            // A diagnostic already went out with the warnings, and says more.
+            if diagnostic.is_none() {
+                eprintln!("{}", error.unwrap_or_default());
+            }
+            exit(1)
+        }
+
         Err(error) => {
             eprintln!("{}", error);
             exit(1)
@@ -66,17 +193,17 @@ fn main() {
     }
 }
 
-fn execute_puzzle(path: &str, function: Operation, variant: &str) -> OperationOutput {
-    let mut buffer = String::new();
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(file);
-    reader.read_to_string(&mut buffer)?;
-
-    let variant = match variant {
-        "1" => Variant::One,
-        "2" => Variant::Two,
-        _ => return Err(From::from(format!("Variant `{}` not recognized", variant))),
-    };
-
-    function(&buffer, variant)
+fn print_row(record: &Record) {
+    match (&record.answer, &record.error) {
+        (Some(answer), _) => println!("{:>3}  {:>4}  {}", record.day, record.part, answer),
+        (None, error) => println!(
+            "{:>3}  {:>4}  error: {}",
+            record.day,
+            record.part,
+            error.as_deref().unwrap_or_default()
+        ),
+    }
+    for event in &record.events {
+        println!("{:>11}  {}", "", event);
+    }
 }
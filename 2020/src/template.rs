@@ -1,10 +1,45 @@
-use crate::{AOCOutput, Variant};
-use std::error::Error;
+use aoc_core::{Input, Result, Solution};
 
-pub fn main(buffer: &String, variant: Variant) -> Result<AOCOutput, Box<dyn Error>> {
+#[doc = include_str!("../puzzles/N.md")]
+pub struct DayN;
 
-    let stderr = format!("Standard Error");
-    let stdout = format!("Standard Output");
+impl Solution for DayN {
+    type Parsed = Vec<String>;
+    type Answer = u32;
 
-    Ok(AOCOutput { stderr, stdout })
+    fn parse(input: &Input) -> Result<Vec<String>> {
+        Ok(input.split('\n').map(String::from).collect())
+    }
+
+    fn part1(_lines: &Vec<String>) -> Result<u32> {
+        todo!()
+    }
+
+    fn part2(_lines: &Vec<String>) -> Result<u32> {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DayN;
+    use aoc_core::examples::{assert_examples, Example};
+
+    // Seed from the puzzle description with `aoc examples 2020/puzzles/N.md`.
+    const EXAMPLES: &[Example] = &[];
+
+    #[test]
+    fn examples() {
+        assert_examples::<DayN>(EXAMPLES);
+    }
+
+    #[test]
+    fn it_works() {
+        crate::assert_answers(N);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(N);
+    }
 }
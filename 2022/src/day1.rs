@@ -1,37 +1,57 @@
+use aoc_core::{
+    error::{self, Error},
+    Input, Result, Solution,
+};
 use itertools::Itertools;
 
-use crate::Part;
-
-pub(crate) fn exec(part: &Part) -> u32 {
-    let contents = include_str!("../inputs/1.txt");
-
-    contents
-        .split("\n\n")
-        .into_iter()
-        .map(|elf_inventory| -> u32 {
-            elf_inventory
-                .split('\n')
-                .into_iter()
-                .map(|food_cal_value| food_cal_value.parse::<u32>().expect(""))
-                .sum()
-        })
-        .sorted()
-        .rev()
-        .take(match part {
-            Part::One => 1,
-            Part::Two => 3,
-        })
-        .sum()
+pub struct Day1;
+
+impl Solution for Day1 {
+    /// Calorie totals for each elf, largest first.
+    type Parsed = Vec<u32>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<u32>> {
+        let totals = input
+            .split("\n\n")
+            .map(|elf_inventory| {
+                elf_inventory
+                    .split('\n')
+                    .map(|food_cal_value| error::number::<u32>(input, food_cal_value))
+                    .sum::<std::result::Result<u32, Error>>()
+            })
+            .collect::<std::result::Result<Vec<u32>, Error>>()?;
+
+        Ok(totals.into_iter().sorted().rev().collect())
+    }
+
+    fn part1(totals: &Vec<u32>) -> Result<u32> {
+        Ok(top_elves(totals, 1))
+    }
+
+    fn part2(totals: &Vec<u32>) -> Result<u32> {
+        Ok(top_elves(totals, 3))
+    }
+}
+
+fn top_elves(totals: &[u32], count: usize) -> u32 {
+    for (rank, total) in totals.iter().enumerate().take(count) {
+        aoc_core::debug!("elf"; rank = rank + 1, calories = total);
+    }
+    let sum = totals.iter().take(count).sum();
+    aoc_core::info!("top {} of {} elves carry {}", count, totals.len(), sum);
+    sum
 }
 
 #[cfg(test)]
 mod tests {
-    use super::exec;
-    use crate::Part;
-
     #[test]
     fn it_works() {
-        assert_eq!(exec(&Part::One), 66186);
-        assert_eq!(exec(&Part::Two), 196804);
+        crate::assert_answers(1);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(1);
     }
 }
@@ -1,8 +1,8 @@
-use itertools::Itertools;
+use std::convert::TryFrom;
 
-use crate::Part;
+use aoc_core::{error::Error, Input, Result, Solution};
 
-enum RPSChoice {
+pub enum RPSChoice {
     Rock,
     Paper,
     Scissors,
@@ -24,13 +24,15 @@ impl GameOutcome {
     }
 }
 
-impl From<&str> for GameOutcome {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for GameOutcome {
+    type Error = Error;
+
+    fn try_from(s: &str) -> std::result::Result<Self, Self::Error> {
         match s {
-            "X" => GameOutcome::Loss,
-            "Y" => GameOutcome::Draw,
-            "Z" => GameOutcome::Win,
-            _ => panic!(),
+            "X" => Ok(GameOutcome::Loss),
+            "Y" => Ok(GameOutcome::Draw),
+            "Z" => Ok(GameOutcome::Win),
+            _ => Err(Error::malformed("an outcome of X, Y or Z")),
         }
     }
 }
@@ -45,13 +47,15 @@ impl RPSChoice {
     }
 }
 
-impl From<&str> for RPSChoice {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for RPSChoice {
+    type Error = Error;
+
+    fn try_from(s: &str) -> std::result::Result<Self, Self::Error> {
         match s {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissors,
-            _ => panic!(),
+            "A" | "X" => Ok(Self::Rock),
+            "B" | "Y" => Ok(Self::Paper),
+            "C" | "Z" => Ok(Self::Scissors),
+            _ => Err(Error::malformed("a move of A, B, C, X, Y or Z")),
         }
     }
 }
@@ -84,40 +88,91 @@ fn get_my_move(opponent_move: &RPSChoice, intended_outcome: &GameOutcome) -> RPS
     }
 }
 
-pub(crate) fn exec(part: &Part) -> u32 {
-    let contents = include_str!("../inputs/2.txt");
-    contents.split('\n').into_iter().fold(0, |acc, strategy| {
-        let v = strategy.split(' ').collect_vec();
-
-        match part {
-            Part::One => {
-                let opponent_move: RPSChoice = v.first().expect("").to_owned().into();
-                let my_move: RPSChoice = v.get(1).expect("").to_owned().into();
-
-                let outcome = perform_round(&my_move, &opponent_move);
-
-                acc + my_move.score() + outcome.score()
-            }
-            Part::Two => {
-                let opponent_move: RPSChoice = v.first().expect("").to_owned().into();
-                let intended_outcome: GameOutcome = v.get(1).expect("").to_owned().into();
+pub struct Day2;
+
+impl Solution for Day2 {
+    /// The opponent's move for each round, alongside the second column of
+    /// the strategy guide, whose meaning differs between the two parts.
+    type Parsed = Vec<(RPSChoice, String)>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<(RPSChoice, String)>> {
+        Ok(input
+            .split('\n')
+            .map(
+                |strategy| match strategy.split(' ').collect::<Vec<_>>()[..] {
+                    [opponent_move, column] => {
+                        let opponent_move = RPSChoice::try_from(opponent_move)
+                            .map_err(|error| error.at(input, opponent_move))?;
+                        // Both parts read the column differently; make sure it
+                        // suits either.
+                        GameOutcome::try_from(column).map_err(|error| error.at(input, column))?;
+                        Ok((opponent_move, column.to_string()))
+                    }
+                    _ => Err(Error::malformed("a round like `A Y`").at(input, strategy)),
+                },
+            )
+            .collect::<std::result::Result<_, Error>>()?)
+    }
 
-                let my_move = get_my_move(&opponent_move, &intended_outcome);
+    fn part1(strategies: &Vec<(RPSChoice, String)>) -> Result<u32> {
+        strategies
+            .iter()
+            .map(|(opponent_move, column)| {
+                let my_move = RPSChoice::try_from(column.as_str())?;
+
+                let outcome = perform_round(&my_move, opponent_move);
+                aoc_core::trace!(
+                    "round";
+                    mine = my_move.score(),
+                    outcome = outcome.score()
+                );
+
+                Ok(my_move.score() + outcome.score())
+            })
+            .sum()
+    }
 
-                acc + my_move.score() + intended_outcome.score()
-            }
-        }
-    })
+    fn part2(strategies: &Vec<(RPSChoice, String)>) -> Result<u32> {
+        strategies
+            .iter()
+            .map(|(opponent_move, column)| {
+                let intended_outcome = GameOutcome::try_from(column.as_str())?;
+
+                let my_move = get_my_move(opponent_move, &intended_outcome);
+                aoc_core::trace!(
+                    "round";
+                    mine = my_move.score(),
+                    outcome = intended_outcome.score()
+                );
+
+                Ok(my_move.score() + intended_outcome.score())
+            })
+            .sum()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::exec;
-    use crate::Part;
+    use super::Day2;
+    use aoc_core::{Input, Solution};
+
+    #[test]
+    fn rejects_unknown_moves() {
+        let error = Day2::parse(&Input::new("A Y\nB W")).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected an outcome of X, Y or Z"
+        );
+    }
 
     #[test]
     fn it_works() {
-        assert_eq!(exec(&Part::One), 15632);
-        assert_eq!(exec(&Part::Two), 14416);
+        crate::assert_answers(2);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(2);
     }
 }
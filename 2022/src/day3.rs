@@ -2,24 +2,41 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-use crate::Part;
+use aoc_core::{error::Error, Input, Result, Solution};
 
-fn get_item_score(item: &char) -> u32 {
+fn get_item_score(item: &char) -> std::result::Result<u32, Error> {
     if item.is_ascii_lowercase() {
-        return item.to_owned() as u8 as u32 - 96;
+        return Ok(item.to_owned() as u8 as u32 - 96);
     } else if item.is_ascii_uppercase() {
-        return item.to_owned() as u8 as u32 - 38;
+        return Ok(item.to_owned() as u8 as u32 - 38);
     }
-    panic!()
+    Err(Error::malformed(format!(
+        "an item from a-z or A-Z, not `{}`",
+        item
+    )))
 }
 
-pub(crate) fn exec(part: &Part) -> u32 {
-    let contents = include_str!("../inputs/3.txt");
-    let lines = contents.split('\n');
+pub struct Day3;
 
-    match part {
-        Part::One => lines
-            .map(|rucksack_contents_str| -> u32 {
+impl Solution for Day3 {
+    type Parsed = Vec<String>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<String>> {
+        for rucksack in input.split('\n') {
+            for (index, item) in rucksack.char_indices() {
+                get_item_score(&item)
+                    .map_err(|error| error.at(input, &rucksack[index..index + item.len_utf8()]))?;
+            }
+        }
+
+        Ok(input.split('\n').map(String::from).collect())
+    }
+
+    fn part1(rucksacks: &Vec<String>) -> Result<u32> {
+        rucksacks
+            .iter()
+            .map(|rucksack_contents_str| -> Result<u32> {
                 let rucksack_contents = rucksack_contents_str.chars().collect_vec();
 
                 let (a, b) = rucksack_contents.split_at(rucksack_contents.len() / 2);
@@ -27,44 +44,60 @@ pub(crate) fn exec(part: &Part) -> u32 {
                 let compt_b: HashSet<char> = HashSet::from_iter(b.iter().cloned());
 
                 let intersection = compt_a.intersection(&compt_b).collect_vec();
-                intersection.iter().map(|item| get_item_score(item)).sum()
+                aoc_core::trace!("{}", rucksack_contents_str; shared = intersection.iter().join(""));
+                Ok(intersection
+                    .iter()
+                    .map(|item| get_item_score(item))
+                    .sum::<std::result::Result<u32, Error>>()?)
             })
-            .sum(),
+            .sum()
+    }
 
-        Part::Two => lines
-            .collect_vec()
+    fn part2(rucksacks: &Vec<String>) -> Result<u32> {
+        rucksacks
             .chunks(3)
-            .map(|elf_group| -> u32 {
-                let a: HashSet<char> = HashSet::from_iter(elf_group[0].chars());
-                let b: HashSet<char> = HashSet::from_iter(elf_group[1].chars());
-                let c: HashSet<char> = HashSet::from_iter(elf_group[2].chars());
+            .map(|elf_group| -> Result<u32> {
+                let (a, b, c) = match elf_group {
+                    [a, b, c] => (a, b, c),
+                    _ => {
+                        return Err(From::from(Error::malformed(
+                            "a number of rucksacks divisible by three",
+                        )))
+                    }
+                };
+                let a: HashSet<char> = HashSet::from_iter(a.chars());
+                let b: HashSet<char> = HashSet::from_iter(b.chars());
+                let c: HashSet<char> = HashSet::from_iter(c.chars());
 
-                HashSet::from_iter(a.intersection(&b).cloned())
+                Ok(HashSet::from_iter(a.intersection(&b).cloned())
                     .intersection(&c)
                     .map(get_item_score)
-                    .sum()
+                    .sum::<std::result::Result<u32, Error>>()?)
             })
-            .sum(),
+            .sum()
     }
 }
 
 #[cfg(test)]
 mod tests {
-
-    use super::{exec, get_item_score};
-    use crate::Part;
+    use super::get_item_score;
 
     #[test]
     fn get_item_score_test() {
-        assert_eq!(get_item_score(&'a'), 1);
-        assert_eq!(get_item_score(&'z'), 26);
-        assert_eq!(get_item_score(&'A'), 27);
-        assert_eq!(get_item_score(&'Z'), 52);
+        assert_eq!(get_item_score(&'a').unwrap(), 1);
+        assert_eq!(get_item_score(&'z').unwrap(), 26);
+        assert_eq!(get_item_score(&'A').unwrap(), 27);
+        assert_eq!(get_item_score(&'Z').unwrap(), 52);
+        assert!(get_item_score(&'1').is_err());
     }
 
     #[test]
     fn it_works() {
-        assert_eq!(exec(&Part::One), 7746);
-        assert_eq!(exec(&Part::Two), 2604);
+        crate::assert_answers(3);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(3);
     }
 }
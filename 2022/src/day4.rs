@@ -1,11 +1,12 @@
 use std::ops::RangeInclusive;
 
-use itertools::Itertools;
+use aoc_core::{
+    error::{self, Error},
+    Input, Result, Solution,
+};
 
-use crate::Part;
-
-#[derive(PartialEq, Eq)]
-enum OverlapStatus {
+#[derive(Debug, PartialEq, Eq)]
+pub enum OverlapStatus {
     FullyContains,
     PartiallyContains,
     NoOverlap,
@@ -30,45 +31,76 @@ where
     OverlapStatus::NoOverlap
 }
 
-pub(crate) fn exec(part: &Part) -> u32 {
-    let contents = include_str!("../inputs/4.txt");
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<OverlapStatus>;
+    type Answer = u32;
 
-    let ranges = contents
-        .lines()
-        .map(|line| {
-            line.split(',')
-                .map(|range| {
-                    range
-                        .split('-')
-                        .map(|s| s.parse().unwrap())
-                        .collect_tuple::<(u32, u32)>()
-                        .unwrap()
-                })
-                .collect_tuple::<((u32, u32), (u32, u32))>()
-                .unwrap()
-        })
-        .map(|((a, b), (c, d))| (RangeInclusive::new(a, b), RangeInclusive::new(c, d)))
-        .map(|(r1, r2)| determine_overlaps(r1, r2));
+    fn parse(input: &Input) -> Result<Vec<OverlapStatus>> {
+        let range = |range: &str| match range.split_once('-') {
+            Some((start, end)) => Ok(RangeInclusive::new(
+                error::number::<u32>(input, start)?,
+                error::number::<u32>(input, end)?,
+            )),
+            None => Err(Error::malformed("a range like `2-4`").at(input, range)),
+        };
+
+        Ok(input
+            .lines()
+            .map(|line| match line.split_once(',') {
+                Some((r1, r2)) => {
+                    let overlap = determine_overlaps(range(r1)?, range(r2)?);
+                    aoc_core::trace!("{}", line; overlap = format!("{:?}", overlap));
+                    Ok(overlap)
+                }
+                None => Err(Error::malformed("a pair like `2-4,6-8`").at(input, line)),
+            })
+            .collect::<std::result::Result<_, Error>>()?)
+    }
+
+    fn part1(overlaps: &Vec<OverlapStatus>) -> Result<u32> {
+        let count = overlaps
+            .iter()
+            .filter(|overlap_status| overlap_status == &&OverlapStatus::FullyContains)
+            .count();
+        aoc_core::info!("{} of {} pairs fully overlap", count, overlaps.len());
+
+        Ok(count as u32)
+    }
 
-    let count = match part {
-        Part::One => ranges
-            .filter(|overlap_status| overlap_status == &OverlapStatus::FullyContains)
-            .count(),
-        Part::Two => ranges
-            .filter(|overlap_status| overlap_status != &OverlapStatus::NoOverlap)
-            .count(),
-    };
+    fn part2(overlaps: &Vec<OverlapStatus>) -> Result<u32> {
+        let count = overlaps
+            .iter()
+            .filter(|overlap_status| overlap_status != &&OverlapStatus::NoOverlap)
+            .count();
+        aoc_core::info!("{} of {} pairs overlap at all", count, overlaps.len());
 
-    count as u32
+        Ok(count as u32)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{day4::exec, Part};
+    use super::Day4;
+    use aoc_core::{Input, Solution};
+
+    #[test]
+    fn rejects_malformed_pairs() {
+        let error = Day4::parse(&Input::new("2-4,6-8\n2-3,4")).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: expected a range like `2-4`"
+        );
+    }
 
     #[test]
     fn it_works() {
-        assert_eq!(exec(&Part::One), 424);
-        assert_eq!(exec(&Part::Two), 804);
+        crate::assert_answers(4);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(4);
     }
 }
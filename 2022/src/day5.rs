@@ -1,12 +1,14 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, fmt, str::FromStr};
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use anyhow::anyhow;
-
-use crate::Part;
+use aoc_core::{
+    error::{self, Error},
+    visual::{self, Frame, View},
+    Input, Result, Solution,
+};
 
 #[derive(Debug)]
 struct Instruction {
@@ -16,92 +18,248 @@ struct Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = anyhow::Error;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+            static ref RE: Regex =
+                Regex::new(r"^move (\d+) from (\d+) to (\d+)$").expect("the pattern is valid");
         }
 
-        let captures = RE.captures(s).ok_or_else(|| anyhow!("asdf"))?;
+        let captures = RE
+            .captures(s)
+            .ok_or_else(|| Error::malformed("a move like `move 1 from 2 to 3`").at(s, s))?;
 
         Ok(Self {
-            crate_quantity: captures[1].parse()?,
-            from_stack: captures[2].parse()?,
-            to_stack: captures[3].parse()?,
+            crate_quantity: error::number(s, &captures[1])?,
+            from_stack: error::number(s, &captures[2])?,
+            to_stack: error::number(s, &captures[3])?,
         })
     }
 }
 
-pub(crate) fn exec(part: &Part) -> String {
-    let contents = include_str!("../inputs/5.txt");
-
-    let (structure, instructions_s) = contents
-        .split("\n\n")
-        .collect_tuple::<(&str, &str)>()
-        .unwrap();
-
-    let mut stacks: HashMap<usize, Vec<char>> = HashMap::new();
-
-    structure
-        .lines()
-        .map(|row| row.chars().skip(1).step_by(4).collect_vec())
-        .rev()
-        .skip(1)
-        .for_each(|row| {
-            row.iter().enumerate().for_each(|(a, b)| {
-                stacks
-                    .entry(a + 1)
-                    .and_modify(|stack| match b {
-                        ' ' => {}
-                        _ => stack.push(b.to_owned()),
-                    })
-                    .or_insert_with(|| vec![b.to_owned()]);
-            })
-        });
-
-    let instructions = instructions_s
-        .lines()
-        .filter_map(|line| Instruction::from_str(line).ok())
-        .collect_vec();
-
-    assert!(instructions.len() == instructions_s.lines().collect_vec().len());
-
-    match part {
-        Part::One => {
-            for instruction in instructions {
-                for _ in 0..instruction.crate_quantity {
-                    let from = stacks.get_mut(&instruction.from_stack).unwrap();
-                    let krate = from.pop().unwrap();
-                    let to = stacks.get_mut(&instruction.to_stack).unwrap();
-                    to.push(krate);
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.crate_quantity, self.from_stack, self.to_stack
+        )
+    }
+}
+
+pub struct Day5;
+
+/// The starting crate stacks, keyed by stack number, and the crane moves to
+/// perform on them.
+pub struct Drawing {
+    stacks: HashMap<usize, Vec<char>>,
+    instructions: Vec<Instruction>,
+}
+
+impl Solution for Day5 {
+    type Parsed = Drawing;
+    type Answer = String;
+
+    fn parse(input: &Input) -> Result<Drawing> {
+        let (structure, instructions_s) = input.split_once("\n\n").ok_or_else(|| {
+            Error::malformed("a drawing of the stacks, a blank line, then the moves")
+        })?;
+
+        let mut stacks: HashMap<usize, Vec<char>> = HashMap::new();
+
+        structure
+            .lines()
+            .map(|row| row.chars().skip(1).step_by(4).collect_vec())
+            .rev()
+            .skip(1)
+            .for_each(|row| {
+                row.iter().enumerate().for_each(|(a, b)| {
+                    stacks
+                        .entry(a + 1)
+                        .and_modify(|stack| match b {
+                            ' ' => {}
+                            _ => stack.push(b.to_owned()),
+                        })
+                        .or_insert_with(|| vec![b.to_owned()]);
+                })
+            });
+
+        let instructions = instructions_s
+            .lines()
+            .map(|line| {
+                let instruction =
+                    Instruction::from_str(line).map_err(|error| error.within(input, line))?;
+                for stack in [instruction.from_stack, instruction.to_stack] {
+                    if !stacks.contains_key(&stack) {
+                        return Err(Error::malformed(format!(
+                            "a stack from 1 to {}, not {}",
+                            stacks.len(),
+                            stack
+                        ))
+                        .at(input, line));
+                    }
                 }
+                Ok(instruction)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(Drawing {
+            stacks,
+            instructions,
+        })
+    }
+
+    fn part1(drawing: &Drawing) -> Result<String> {
+        let mut stacks = drawing.stacks.clone();
+        visual::frame(|| picture(&stacks, "the starting stacks".to_string()));
+        for instruction in &drawing.instructions {
+            aoc_core::debug!(
+                "move";
+                count = instruction.crate_quantity,
+                from = instruction.from_stack,
+                to = instruction.to_stack
+            );
+            for _ in 0..instruction.crate_quantity {
+                let from = stack(&mut stacks, instruction.from_stack)?;
+                let krate = from.pop().ok_or_else(|| {
+                    Error::unsolvable(format!(
+                        "stack {} ran out of crates",
+                        instruction.from_stack
+                    ))
+                })?;
+                let to = stack(&mut stacks, instruction.to_stack)?;
+                to.push(krate);
             }
+            visual::frame(|| picture(&stacks, instruction.to_string()));
         }
-        Part::Two => {
-            for instruction in instructions {
-                let from = stacks.get_mut(&instruction.from_stack).unwrap();
-                let split_point = from.len() - instruction.crate_quantity;
-                let mut picked_up = from.split_off(split_point);
-                let to = stacks.get_mut(&instruction.to_stack).unwrap();
-                to.append(&mut picked_up);
-            }
+
+        top_crates(&stacks)
+    }
+
+    fn part2(drawing: &Drawing) -> Result<String> {
+        let mut stacks = drawing.stacks.clone();
+        visual::frame(|| picture(&stacks, "the starting stacks".to_string()));
+        for instruction in &drawing.instructions {
+            aoc_core::debug!(
+                "move";
+                count = instruction.crate_quantity,
+                from = instruction.from_stack,
+                to = instruction.to_stack
+            );
+            let from = stack(&mut stacks, instruction.from_stack)?;
+            let split_point = from
+                .len()
+                .checked_sub(instruction.crate_quantity)
+                .ok_or_else(|| {
+                    Error::unsolvable(format!(
+                        "stack {} has fewer than {} crates",
+                        instruction.from_stack, instruction.crate_quantity
+                    ))
+                })?;
+            let mut picked_up = from.split_off(split_point);
+            let to = stack(&mut stacks, instruction.to_stack)?;
+            to.append(&mut picked_up);
+            visual::frame(|| picture(&stacks, instruction.to_string()));
         }
+
+        top_crates(&stacks)
     }
+}
+
+fn stack(
+    stacks: &mut HashMap<usize, Vec<char>>,
+    number: usize,
+) -> std::result::Result<&mut Vec<char>, Error> {
+    stacks
+        .get_mut(&number)
+        .ok_or_else(|| Error::unsolvable(format!("there's no stack {}", number)))
+}
 
-    (1..=9)
-        .map(|stack_idx| stacks.get(&stack_idx).unwrap().last().unwrap())
-        .join("")
+/// The stacks side by side in number order, captioned with what just
+/// happened to them.
+fn picture(stacks: &HashMap<usize, Vec<char>>, caption: String) -> Frame {
+    Frame {
+        caption,
+        view: View::Stacks(
+            stacks
+                .keys()
+                .sorted()
+                .map(|number| stacks[number].clone())
+                .collect(),
+        ),
+    }
+}
+
+fn top_crates(stacks: &HashMap<usize, Vec<char>>) -> Result<String> {
+    for (number, stack) in stacks.iter().sorted() {
+        aoc_core::info!("{}: {}", number, stack.iter().collect::<String>());
+    }
+
+    Ok(stacks
+        .keys()
+        .sorted()
+        .map(|stack_idx| {
+            stacks[stack_idx]
+                .last()
+                .ok_or_else(|| Error::unsolvable(format!("stack {} ended up empty", stack_idx)))
+        })
+        .collect::<std::result::Result<String, _>>()?)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::exec;
-    use crate::Part;
+    use super::Day5;
+    use aoc_core::{
+        visual::{self, View},
+        Input, Solution,
+    };
+
+    const EXAMPLE: &str =
+        "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
+
+    #[test]
+    fn points_at_malformed_moves() {
+        let drawing = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\nmove one from 1 to 2";
+        let error = Day5::parse(&Input::new(drawing)).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 6, column 1: expected a move like `move 1 from 2 to 3`"
+        );
+    }
+
+    #[test]
+    fn draws_the_stacks_after_each_move() {
+        let drawing = Day5::parse(&Input::new(EXAMPLE)).unwrap();
+        visual::set_enabled(true);
+        let (answer, frames) = visual::capture(|| Day5::part1(&drawing));
+        visual::set_enabled(false);
+
+        assert_eq!(answer.unwrap(), "CMZ");
+        let captions: Vec<_> = frames.iter().map(|frame| frame.caption.as_str()).collect();
+        assert_eq!(
+            captions,
+            [
+                "the starting stacks",
+                "move 1 from 2 to 1",
+                "move 3 from 1 to 3",
+                "move 2 from 2 to 1",
+                "move 1 from 1 to 2"
+            ]
+        );
+        assert_eq!(
+            frames[4].view,
+            View::Stacks(vec![vec!['C'], vec!['M'], vec!['P', 'D', 'N', 'Z']])
+        );
+    }
 
     #[test]
     fn it_works() {
-        assert_eq!(exec(&Part::One), "LJSVLTWQM");
-        assert_eq!(exec(&Part::Two), "BRQWDBBJM");
+        crate::assert_answers(5);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(5);
     }
 }
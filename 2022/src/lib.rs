@@ -0,0 +1,64 @@
+use aoc_core::{answers::Answers, Entry, Result};
+use std::path::Path;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+
+pub const YEAR: u16 = 2022;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Day1>(YEAR, 1),
+        Entry::new::<day2::Day2>(YEAR, 2),
+        Entry::new::<day3::Day3>(YEAR, 3),
+        Entry::new::<day4::Day4>(YEAR, 4),
+        Entry::new::<day5::Day5>(YEAR, 5),
+    ]
+}
+
+/// Where each day's input lives unless the runner is pointed elsewhere.
+pub const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/inputs");
+
+/// Where each day's puzzle description lives, as `N.md`.
+pub const PUZZLE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/puzzles");
+
+/// Accepted answers, shared by the tests and `aoc verify`.
+pub const ANSWERS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+pub fn answers() -> Result<Answers> {
+    Answers::load(Path::new(ANSWERS_PATH))
+}
+
+#[cfg(test)]
+fn assert_answers(day: u8) {
+    use aoc_core::answers::{assert_day, assert_solution};
+    assert_solution(assert_day, solutions(), input, answers(), day);
+}
+
+#[cfg(test)]
+fn assert_variants(day: u8) {
+    use aoc_core::answers::{assert_solution, assert_variants};
+    assert_solution(assert_variants, solutions(), input, answers(), day);
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+pub fn input(day: u8) -> Result<String> {
+    aoc_core::input::read(&aoc_core::input::default_path(INPUT_DIR, day))
+}
+
+#[cfg(feature = "embed-inputs")]
+pub fn input(day: u8) -> Result<String> {
+    let contents = match day {
+        1 => include_str!("../inputs/1.txt"),
+        2 => include_str!("../inputs/2.txt"),
+        3 => include_str!("../inputs/3.txt"),
+        4 => include_str!("../inputs/4.txt"),
+        5 => include_str!("../inputs/5.txt"),
+        _ => return Err(From::from(format!("No input embedded for day {}", day))),
+    };
+
+    Ok(contents.to_string())
+}
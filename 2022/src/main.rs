@@ -1,44 +1,170 @@
-use std::{fmt::Display, time::Instant};
+use std::{path::Path, process::exit};
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
+use aoc_2022::{input, solutions};
+use aoc_core::{
+    bench, cli,
+    input::read,
+    report::{Format, FORMATS},
+    runner::{self, Record, Status},
+    sandbox,
+    select::{parse_days, parse_parts},
+    visual,
+};
+use clap::{App, Arg};
 
-enum Part {
-    One,
-    Two,
-}
+fn main() {
+    sandbox::serve(&solutions());
+
+    let matches = App::new("Advent of Code")
+        .version("2022")
+        .about("https://adventofcode.com")
+        .arg(
+            Arg::new("day")
+                .short('d')
+                .long("day")
+                .takes_value(true)
+                .help("A single day (3) or an inclusive range (1-5)"),
+        )
+        .arg(
+            Arg::new("latest")
+                .long("latest")
+                .conflicts_with("day")
+                .help("Only run the most recent day"),
+        )
+        .arg(
+            Arg::new("part")
+                .short('p')
+                .long("part")
+                .takes_value(true)
+                .possible_values(["1", "2"]),
+        )
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .takes_value(true)
+                .requires("day")
+                .help("Read this file in place of the day's input; needs a single --day"),
+        )
+        .args(cli::trace_args(true))
+        .args(cli::visual_args())
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .takes_value(true)
+                .default_value("human")
+                .possible_values(FORMATS),
+        )
+        .args(cli::bench_args())
+        .args(cli::sandbox_args())
+        .get_matches();
 
-impl Display for Part {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(match self {
-            Part::One => "1",
-            Part::Two => "2",
-        })
+    let days = match matches.value_of("day").map(parse_days).transpose() {
+        Ok(days) => days,
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    };
+    if let (Some(days), Some(_)) = (&days, matches.value_of("input")) {
+        if days.start() != days.end() {
+            eprintln!(
+                "--input can only stand in for a single day, not {}",
+                matches.value_of("day").unwrap()
+            );
+            exit(1);
+        }
     }
-}
+    let parts = match parse_parts(matches.value_of("part")) {
+        Ok(parts) => parts,
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    };
+    let format: Format = matches.value_of_t_or_exit("format");
+    let limits = cli::limits(&matches);
+    cli::set_trace_level(&matches);
+    let playback = cli::playback(&matches);
 
-fn main() {
-    run_fn("1", &day1::exec);
-    run_fn("2", &day2::exec);
-    run_fn("3", &day3::exec);
-    run_fn("4", &day4::exec);
-    run_fn("5", &day5::exec);
+    let mut entries = solutions();
+    if matches.is_present("latest") {
+        entries = entries.split_off(entries.len().saturating_sub(1));
+    }
+    if let Some(days) = days {
+        entries.retain(|entry| days.contains(&entry.day));
+        if entries.is_empty() {
+            eprintln!("No solutions for day {}", matches.value_of("day").unwrap());
+            exit(1);
+        }
+    }
+
+    let contents = |day| match matches.value_of("input") {
+        Some(path) => read(Path::new(path)),
+        None => input(day),
+    };
+
+    if let Some(options) = cli::bench_options(&matches) {
+        let mut failed = false;
+        bench::print_header(format);
+        for entry in entries {
+            match contents(entry.day)
+                .and_then(|contents| bench::bench_and_record(&entry, &contents, &parts, &options))
+            {
+                Ok(measurements) => bench::print(format, &measurements),
+                Err(error) => {
+                    failed = true;
+                    eprintln!("Day {}: {}", entry.day, error);
+                }
+            }
+        }
+        exit(if failed { 1 } else { 0 });
+    }
+
+    if let Some(header) = format.header::<Record>() {
+        println!("{}", header);
+    }
+
+    let mut failed = false;
+    for entry in entries {
+        let contents = contents(entry.day);
+
+        let records = match contents {
+            Ok(contents) => match &limits {
+                Some(limits) => sandbox::run(&entry, &contents, &parts, limits),
+                None => runner::run(&entry, &contents, &parts),
+            },
+            Err(error) => runner::failed(&entry, &parts, &error),
+        };
+
+        for record in &records {
+            if let Some(playback) = playback {
+                visual::play(&record.frames, playback, format.screen());
+            }
+            failed |= record.status != Status::Ok;
+            match format.line(record) {
+                Some(line) => println!("{}", line),
+                None => print_human(record),
+            }
+        }
+        runner::warn(&records);
+    }
+
+    exit(if failed { 1 } else { 0 })
 }
 
-fn run_fn<T>(day: &str, f: &dyn Fn(&Part) -> T)
-where
-    T: ToString,
-{
-    for part in &vec![Part::One, Part::Two] {
-        let start = Instant::now();
-        let retval = f(part);
-        println!(
+fn print_human(record: &Record) {
+    let Record { day, part, .. } = record;
+    match (&record.answer, &record.error) {
+        (Some(answer), _) => println!(
             "Day {day} part {part}: {} ({} us)",
-            retval.to_string(),
-            start.elapsed().as_micros()
-        )
+            answer,
+            record.elapsed.as_micros()
+        ),
+        (None, Some(error)) => println!("Day {day} part {part}: {error}"),
+        (None, None) => {}
+    }
+    for event in &record.events {
+        println!("    {}", event);
     }
 }
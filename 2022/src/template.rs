@@ -1,24 +1,34 @@
-use crate::Part;
+use aoc_core::{Input, Result, Solution};
 
-pub(crate) fn exec(part: &Part) -> u32 {
-    let contents = include_str!("../inputs/n.txt");
+#[doc = include_str!("../puzzles/N.md")]
+pub struct DayN;
 
-    match part {
-        Part::One => todo!(),
-        Part::Two => todo!(),
+impl Solution for DayN {
+    type Parsed = Vec<String>;
+    type Answer = u32;
+
+    fn parse(input: &Input) -> Result<Vec<String>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(_lines: &Vec<String>) -> Result<u32> {
+        todo!()
     }
 
-    0
+    fn part2(_lines: &Vec<String>) -> Result<u32> {
+        todo!()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::exec;
-    use crate::Part;
-
     #[test]
     fn it_works() {
-        assert_eq!(exec(&Part::One), 0);
-        assert_eq!(exec(&Part::Two), 0);
+        crate::assert_answers(N);
+    }
+
+    #[test]
+    fn tolerates_crlf_bom_and_trailing_newlines() {
+        crate::assert_variants(N);
     }
 }
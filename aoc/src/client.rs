@@ -0,0 +1,250 @@
+use std::{
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_core::{state, Result};
+
+use crate::config::Config;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Sent with every request so the site can tell who is calling it.
+pub const USER_AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (James Little's Advent of Code runner; fetches each input once)"
+);
+
+/// Never hit the site more often than this, even across separate runs.
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A throttled, authenticated connection to adventofcode.com (or whatever
+/// stands in for it).
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    interval: Duration,
+    /// Records when the last request went out, so the throttle holds between
+    /// invocations.
+    stamp: PathBuf,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self> {
+        let session = config
+            .session
+            .clone()
+            .ok_or("No session token; set AOC_SESSION or `session` in .aoc/config.toml")?;
+
+        Ok(Client::with(
+            config.base_url.as_deref().unwrap_or(BASE_URL),
+            &session,
+            config.user_agent.as_deref().unwrap_or(USER_AGENT),
+            MIN_INTERVAL,
+            state::path("last-request"),
+        ))
+    }
+
+    fn with(
+        base_url: &str,
+        session: &str,
+        user_agent: &str,
+        interval: Duration,
+        stamp: PathBuf,
+    ) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(user_agent)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            interval,
+            stamp,
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<String> {
+        self.throttle()?;
+        let response = self
+            .agent
+            .get(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|error| describe(path, error))?;
+        Ok(response.into_string()?)
+    }
+
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.throttle()?;
+        let response = self
+            .agent
+            .post(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form)
+            .map_err(|error| describe(path, error))?;
+        Ok(response.into_string()?)
+    }
+
+    /// Sleeps until `interval` has passed since the last request, then
+    /// stamps this one.
+    fn throttle(&self) -> Result<()> {
+        let last = fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|stamp| stamp.trim().parse().ok())
+            .map(Duration::from_nanos);
+
+        if let Some(elapsed) = last.and_then(|last| now().checked_sub(last)) {
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+
+        if let Some(dir) = self.stamp.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.stamp, now().as_nanos().to_string())?;
+        Ok(())
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+fn describe(path: &str, error: ureq::Error) -> Box<dyn std::error::Error> {
+    match error {
+        ureq::Error::Status(400, _) | ureq::Error::Status(500, _) => From::from(format!(
+            "Request for {} was refused; is the session token still valid?",
+            path
+        )),
+        ureq::Error::Status(404, _) => From::from(format!("{} doesn't exist (yet?)", path)),
+        ureq::Error::Status(code, _) => {
+            From::from(format!("Request for {} failed with status {}", path, code))
+        }
+        error => From::from(format!("Request for {} failed: {}", path, error)),
+    }
+}
+
+/// A one-shot HTTP server standing in for adventofcode.com in tests.
+#[cfg(test)]
+pub(crate) mod stand_in {
+    use std::{
+        env,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        path::{Path, PathBuf},
+        process,
+        sync::mpsc::{self, Receiver},
+        thread,
+        time::Duration,
+    };
+
+    use super::Client;
+
+    /// Serves `responses` (status, body) in order, passing each raw request
+    /// it receives back through the returned channel.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                sender.send(request).unwrap();
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    /// A scratch directory unique to one test.
+    pub fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{}-{}", process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    pub fn client(base_url: &str, interval: Duration, scratch: &Path) -> Client {
+        Client::with(
+            base_url,
+            "cookie",
+            "aoc-tests",
+            interval,
+            scratch.join("last-request"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::stand_in::{client, scratch, serve};
+
+    #[test]
+    fn sends_session_and_user_agent() {
+        let (base_url, requests) = serve(vec![(200, "puzzle input")]);
+        let client = client(&base_url, Duration::ZERO, &scratch("headers"));
+
+        assert_eq!(client.get("/2022/day/1/input").unwrap(), "puzzle input");
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.starts_with("get /2022/day/1/input "));
+        assert!(request.contains("cookie: session=cookie\r\n"));
+        assert!(request.contains("user-agent: aoc-tests\r\n"));
+    }
+
+    #[test]
+    fn throttles_consecutive_requests() {
+        let (base_url, _requests) = serve(vec![(200, "1"), (200, "2")]);
+        let client = client(&base_url, Duration::from_millis(300), &scratch("throttle"));
+
+        let start = Instant::now();
+        client.get("/1").unwrap();
+        client.get("/2").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn explains_refusals() {
+        let (base_url, _requests) = serve(vec![(400, "Puzzle inputs differ by user.")]);
+        let client = client(&base_url, Duration::ZERO, &scratch("refused"));
+
+        let error = client.get("/2022/day/1/input").unwrap_err();
+        assert!(error.to_string().contains("session token"));
+    }
+}
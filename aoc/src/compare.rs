@@ -0,0 +1,79 @@
+use std::process::exit;
+
+use aoc_core::history::{self, Verdict};
+use clap::{App, Arg, ArgMatches};
+
+pub fn command() -> App<'static> {
+    App::new("compare")
+        .about("Flags days whose benchmark times regressed against a baseline")
+        .arg(
+            Arg::new("baseline")
+                .short('b')
+                .long("baseline")
+                .takes_value(true)
+                .help("Commit (or prefix) to compare against; defaults to the latest other commit"),
+        )
+        .arg(
+            Arg::new("threshold")
+                .short('t')
+                .long("threshold")
+                .takes_value(true)
+                .default_value("10")
+                .help("Percent change in median time that counts as a regression"),
+        )
+        .arg(Arg::new("year").short('y').long("year").takes_value(true))
+        .arg(Arg::new("day").short('d').long("day").takes_value(true))
+}
+
+pub fn exec(matches: &ArgMatches) {
+    let threshold: f64 = matches.value_of_t_or_exit("threshold");
+    let runs = match history::load() {
+        Ok(runs) => runs,
+        Err(error) => {
+            eprintln!("Couldn't load benchmark history: {}", error);
+            exit(1);
+        }
+    };
+
+    let year = matches.value_of("year");
+    let day = matches.value_of("day");
+    let comparisons: Vec<_> = history::compare(&runs, matches.value_of("baseline"), threshold)
+        .into_iter()
+        .filter(|comparison| year.is_none_or(|year| comparison.year.to_string() == year))
+        .filter(|comparison| day.is_none_or(|day| comparison.day.to_string() == day))
+        .collect();
+
+    if comparisons.is_empty() {
+        eprintln!("No benchmark history to compare; run with --bench on two commits first");
+        exit(1);
+    }
+
+    println!(
+        "{:>4}  {:>3}  {:<5}  {:>11}  {:>11}  {:>8}",
+        "Year", "Day", "Stage", "Baseline", "Current", "Change"
+    );
+    for comparison in &comparisons {
+        let flag = match comparison.verdict {
+            Verdict::Regressed => "REGRESSED",
+            Verdict::Improved => "improved",
+            Verdict::Unchanged => "",
+        };
+        println!(
+            "{:>4}  {:>3}  {:<5}  {:>11}  {:>11}  {:>+7.1}%  {} ({} -> {})",
+            comparison.year,
+            comparison.day,
+            comparison.stage,
+            format!("{:.2?}", comparison.baseline.measurement.stats.median),
+            format!("{:.2?}", comparison.current.measurement.stats.median),
+            comparison.change,
+            flag,
+            comparison.baseline.commit,
+            comparison.current.commit,
+        );
+    }
+
+    let regressed = comparisons
+        .iter()
+        .any(|comparison| comparison.verdict == Verdict::Regressed);
+    exit(if regressed { 1 } else { 0 })
+}
@@ -0,0 +1,35 @@
+use std::{env, fs};
+
+use aoc_core::{state, Result};
+use serde::Deserialize;
+
+/// Settings for talking to adventofcode.com, read from `config.toml` in the
+/// state directory. `AOC_SESSION` and `AOC_BASE_URL` override the file.
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+pub fn load() -> Result<Config> {
+    let path = state::path("config.toml");
+    let mut config: Config = match fs::read_to_string(&path) {
+        Ok(contents) => toml::from_str(&contents)
+            .map_err(|error| format!("Couldn't parse `{}`: {}", path.display(), error))?,
+        Err(_) => Config::default(),
+    };
+
+    if let Ok(session) = env::var("AOC_SESSION") {
+        config.session = Some(session);
+    }
+    if let Ok(base_url) = env::var("AOC_BASE_URL") {
+        config.base_url = Some(base_url);
+    }
+
+    Ok(config)
+}
@@ -0,0 +1,39 @@
+use std::{fs, process::exit};
+
+use aoc_core::examples;
+use clap::{App, Arg, ArgMatches};
+
+pub fn command() -> App<'static> {
+    App::new("examples")
+        .about("Prints candidate example blocks from a day's puzzle description as test fixtures")
+        .arg(
+            Arg::new("source")
+                .required(true)
+                .multiple_occurrences(true)
+                .help("Puzzle description to scan, e.g. 2020/puzzles/1.md"),
+        )
+}
+
+pub fn exec(matches: &ArgMatches) {
+    let mut found = false;
+    for path in matches.values_of("source").into_iter().flatten() {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(error) => {
+                eprintln!("Couldn't read `{}`: {}", path, error);
+                exit(1);
+            }
+        };
+
+        for candidate in examples::extract(&source) {
+            found = true;
+            println!("// {}:{}", path, candidate.line);
+            println!("{}", candidate.to_fixture());
+        }
+    }
+
+    if !found {
+        eprintln!("No example blocks found");
+        exit(1);
+    }
+}
@@ -0,0 +1,114 @@
+use std::{fs, path::Path, process::exit};
+
+use aoc_core::{input, Result};
+use clap::{App, Arg, ArgMatches};
+
+use crate::{client::Client, config, registry};
+
+pub fn command() -> App<'static> {
+    App::new("fetch")
+        .about("Downloads a day's puzzle input into its year's inputs directory")
+        .arg(
+            Arg::new("year")
+                .short('y')
+                .long("year")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("day")
+                .short('d')
+                .long("day")
+                .required(true)
+                .takes_value(true),
+        )
+}
+
+pub fn exec(matches: &ArgMatches) {
+    let year: u16 = matches.value_of_t_or_exit("year");
+    let day: u8 = matches.value_of_t_or_exit("day");
+    let dir = match registry::input_dir(year) {
+        Some(dir) => dir,
+        None => {
+            eprintln!("There's no crate for {}", year);
+            exit(1);
+        }
+    };
+
+    let path = input::default_path(dir, day);
+    if cached(&path) {
+        println!("Already have {}", path.display());
+        return;
+    }
+
+    let fetched = config::load()
+        .and_then(|config| Client::new(&config))
+        .and_then(|client| fetch(&client, year, day, &path));
+
+    match fetched {
+        Ok(true) => println!("Saved {}", path.display()),
+        Ok(false) => println!("Already have {}", path.display()),
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    }
+}
+
+/// Downloads the input to `path` unless it's already there, returning
+/// whether it did. Inputs never change, so a non-empty file is never
+/// fetched again; an empty one (as left by `aoc new`) is.
+pub fn fetch(client: &Client, year: u16, day: u8, path: &Path) -> Result<bool> {
+    if cached(path) {
+        return Ok(false);
+    }
+
+    let contents = client.get(&format!("/{}/day/{}/input", year, day))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(true)
+}
+
+fn cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use super::fetch;
+    use crate::client::stand_in::{client, scratch, serve};
+
+    #[test]
+    fn downloads_once() {
+        let (base_url, requests) = serve(vec![(200, "1000\n2000\n")]);
+        let scratch = scratch("fetch");
+        let client = client(&base_url, Duration::ZERO, &scratch);
+        let path = scratch.join("inputs/1.txt");
+
+        assert!(fetch(&client, 2022, 1, &path).unwrap());
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /2022/day/1/input "));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+        // The stand-in only answers once; a second request would fail.
+        assert!(!fetch(&client, 2022, 1, &path).unwrap());
+    }
+
+    #[test]
+    fn replaces_empty_placeholders() {
+        let (base_url, _requests) = serve(vec![(200, "input")]);
+        let scratch = scratch("placeholder");
+        let client = client(&base_url, Duration::ZERO, &scratch);
+        let path = scratch.join("6.txt");
+        fs::write(&path, "").unwrap();
+
+        assert!(fetch(&client, 2022, 6, &path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "input");
+    }
+}
@@ -0,0 +1,175 @@
+use std::{path::Path, process::exit};
+
+use aoc_core::{
+    bench, cli, input,
+    report::{Format, FORMATS},
+    runner::{self, Record, Status},
+    sandbox,
+    select::parse_parts,
+    visual,
+};
+use clap::{App, AppSettings, Arg, ArgMatches};
+
+mod client;
+mod compare;
+mod config;
+mod examples;
+mod fetch;
+mod new;
+mod registry;
+mod show;
+mod submit;
+mod verify;
+mod watch;
+
+fn main() {
+    sandbox::serve(&registry::entries());
+
+    let matches = App::new("Advent of Code")
+        .author("James Little")
+        .about("Runs any solved day from any year")
+        .arg(
+            Arg::new("year")
+                .short('y')
+                .long("year")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("day")
+                .short('d')
+                .long("day")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("part")
+                .short('p')
+                .long("part")
+                .takes_value(true)
+                .possible_values(["1", "2"]),
+        )
+        .arg(Arg::new("input").short('i').long("input").takes_value(true))
+        .args(cli::trace_args(true))
+        .args(cli::visual_args())
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .takes_value(true)
+                .default_value("human")
+                .possible_values(FORMATS),
+        )
+        .args(cli::bench_args())
+        .args(cli::sandbox_args())
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .setting(AppSettings::ArgsNegateSubcommands)
+        .subcommand(compare::command())
+        .subcommand(examples::command())
+        .subcommand(fetch::command())
+        .subcommand(new::command())
+        .subcommand(show::command())
+        .subcommand(submit::command())
+        .subcommand(verify::command())
+        .subcommand(watch::command())
+        .get_matches();
+
+    match matches.subcommand() {
+        Some(("compare", matches)) => compare::exec(matches),
+        Some(("examples", matches)) => examples::exec(matches),
+        Some(("fetch", matches)) => fetch::exec(matches),
+        Some(("new", matches)) => new::exec(matches),
+        Some(("show", matches)) => show::exec(matches),
+        Some(("submit", matches)) => submit::exec(matches),
+        Some(("verify", matches)) => verify::exec(matches),
+        Some(("watch", matches)) => watch::exec(matches),
+        _ => run(&matches),
+    }
+}
+
+fn run(matches: &ArgMatches) {
+    let year: u16 = matches.value_of_t_or_exit("year");
+    let day: u8 = matches.value_of_t_or_exit("day");
+    let parts = parse_parts(matches.value_of("part")).unwrap();
+    let format: Format = matches.value_of_t_or_exit("format");
+    cli::set_trace_level(matches);
+    let playback = cli::playback(matches);
+
+    let entry = registry::find(year, day).unwrap_or_else(|| {
+        eprintln!("No solution registered for {} day {}", year, day);
+        exit(1);
+    });
+
+    let contents = match matches.value_of("input") {
+        Some(path) => input::read(Path::new(path)),
+        None => registry::input(&entry),
+    };
+
+    if let Some(options) = cli::bench_options(matches) {
+        match contents
+            .and_then(|contents| bench::bench_and_record(&entry, &contents, &parts, &options))
+        {
+            Ok(measurements) => {
+                bench::print_header(format);
+                bench::print(format, &measurements);
+                exit(0);
+            }
+            Err(error) => {
+                eprintln!("{} day {}: {}", year, day, error);
+                exit(1);
+            }
+        }
+    }
+
+    let records = match contents {
+        Ok(contents) => match cli::limits(matches) {
+            Some(limits) => sandbox::run(&entry, &contents, &parts, &limits),
+            None => runner::run(&entry, &contents, &parts),
+        },
+        Err(error) => runner::failed(&entry, &parts, &error),
+    };
+
+    if let Some(header) = format.header::<Record>() {
+        println!("{}", header);
+    }
+
+    let mut failed = false;
+    for record in &records {
+        if let Some(playback) = playback {
+            visual::play(&record.frames, playback, format.screen());
+        }
+        failed |= record.status != Status::Ok;
+        match format.line(record) {
+            Some(line) => println!("{}", line),
+            None => print_human(record),
+        }
+    }
+    runner::warn(&records);
+
+    exit(if failed { 1 } else { 0 })
+}
+
+fn print_human(record: &Record) {
+    let Record {
+        year, day, part, ..
+    } = record;
+    match (&record.answer, &record.error) {
+        (Some(answer), _) => {
+            for event in &record.events {
+                eprintln!("{}", event);
+            }
+            println!("{} day {} part {}: {}", year, day, part, answer);
+        }
+        (None, error) => {
+            for event in &record.events {
+                eprintln!("{}", event);
+            }
+            eprintln!(
+                "{} day {} part {}: {}",
+                year,
+                day,
+                part,
+                error.as_deref().unwrap_or_default()
+            )
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::{fs, path::Path, process::exit};
+
+use aoc_core::Result;
+use clap::{App, Arg, ArgMatches};
+
+use crate::registry;
+
+pub fn command() -> App<'static> {
+    App::new("new")
+        .about("Scaffolds a day from its year's template and registers it")
+        .arg(
+            Arg::new("year")
+                .short('y')
+                .long("year")
+                .required(true)
+                .takes_value(true)
+                .possible_values(["2020", "2022"]),
+        )
+        .arg(
+            Arg::new("day")
+                .short('d')
+                .long("day")
+                .required(true)
+                .takes_value(true),
+        )
+}
+
+pub fn exec(matches: &ArgMatches) {
+    let year: u16 = matches.value_of_t_or_exit("year");
+    let day: u8 = matches.value_of_t_or_exit("day");
+
+    if let Err(error) = scaffold(&registry::crate_dir(year), day) {
+        eprintln!("{}", error);
+        exit(1);
+    }
+}
+
+fn scaffold(crate_dir: &Path, day: u8) -> Result<()> {
+    let src = crate_dir.join("src");
+    let module = src.join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(From::from(format!("{} already exists", module.display())));
+    }
+
+    let template = read(&src.join("template.rs"))?;
+    let lib_path = src.join("lib.rs");
+    let lib = register(&read(&lib_path)?, day)?;
+
+    write(&module, &instantiate(&template, day))?;
+    write(&lib_path, &lib)?;
+    println!("Created {}", module.display());
+    println!("Registered day {} in {}", day, lib_path.display());
+
+    let input = crate_dir.join("inputs").join(format!("{}.txt", day));
+    let puzzle = crate_dir.join("puzzles").join(format!("{}.md", day));
+    // The template documents the day with its description, and rustdoc
+    // lints against an empty one, so that starts out with a heading.
+    let placeholders = [(input, String::new()), (puzzle, format!("# Day {}\n", day))];
+    for (placeholder, contents) in placeholders {
+        if !placeholder.exists() {
+            if let Some(dir) = placeholder.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            write(&placeholder, &contents)?;
+            println!("Created {}", placeholder.display());
+        }
+    }
+
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .map_err(|error| From::from(format!("Couldn't read `{}`: {}", path.display(), error)))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents)
+        .map_err(|error| From::from(format!("Couldn't write `{}`: {}", path.display(), error)))
+}
+
+/// Fills the template's `DayN`/`dayN`/`N` placeholders in with `day`.
+fn instantiate(template: &str, day: u8) -> String {
+    template
+        .replace("DayN", &format!("Day{}", day))
+        .replace("dayN", &format!("day{}", day))
+        .replace("(N)", &format!("({})", day))
+        .replace("/N.md", &format!("/{}.md", day))
+}
+
+/// Adds `day` to a year's `lib.rs`: its `mod` declaration, its entry in
+/// `solutions()`, and its `include_str!` arm when the year can embed inputs.
+fn register(lib: &str, day: u8) -> Result<String> {
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+    insert(&mut lines, day, format!("pub mod day{};", day), |line| {
+        line.strip_prefix("pub mod day")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    })
+    .ok_or("Couldn't find the `pub mod dayN;` declarations")?;
+
+    insert(
+        &mut lines,
+        day,
+        format!("        Entry::new::<day{0}::Day{0}>(YEAR, {0}),", day),
+        |line| {
+            let rest = line.trim_start().strip_prefix("Entry::new::<day")?;
+            rest.split("::").next()?.parse().ok()
+        },
+    )
+    .ok_or("Couldn't find the `Entry::new` list in `solutions()`")?;
+
+    // Only some years support baking inputs in, so a missing list is fine.
+    insert(
+        &mut lines,
+        day,
+        format!("        {0} => include_str!(\"../inputs/{0}.txt\"),", day),
+        |line| {
+            let (day, _) = line.trim_start().split_once(" => include_str!")?;
+            day.parse().ok()
+        },
+    );
+
+    let mut lib = lines.join("\n");
+    lib.push('\n');
+    Ok(lib)
+}
+
+/// Inserts `new_line` among the lines `key` recognises, keeping them in day
+/// order. Returns `None` when there are no such lines to insert among.
+fn insert(
+    lines: &mut Vec<String>,
+    day: u8,
+    new_line: String,
+    key: impl Fn(&str) -> Option<u8>,
+) -> Option<()> {
+    let keyed: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, key(line)?)))
+        .collect();
+
+    if keyed.iter().any(|&(_, existing)| existing == day) {
+        return Some(());
+    }
+
+    let index = match keyed.iter().rev().find(|&&(_, existing)| existing < day) {
+        Some(&(index, _)) => index + 1,
+        None => keyed.first()?.0,
+    };
+    lines.insert(index, new_line);
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{instantiate, register};
+
+    const LIB: &str = "use aoc_core::{Entry, Result};
+
+pub mod day1;
+pub mod day3;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Day1>(YEAR, 1),
+        Entry::new::<day3::Day3>(YEAR, 3),
+    ]
+}
+";
+
+    #[test]
+    fn registers_in_day_order() {
+        assert_eq!(
+            register(LIB, 2).unwrap(),
+            "use aoc_core::{Entry, Result};
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+
+pub fn solutions() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Day1>(YEAR, 1),
+        Entry::new::<day2::Day2>(YEAR, 2),
+        Entry::new::<day3::Day3>(YEAR, 3),
+    ]
+}
+"
+        );
+    }
+
+    #[test]
+    fn registers_embedded_inputs() {
+        let lib = format!(
+            "{}\nfn input(day: u8) {{\n    match day {{\n        1 => include_str!(\"../inputs/1.txt\"),\n    }}\n}}\n",
+            LIB
+        );
+        assert!(register(&lib, 4)
+            .unwrap()
+            .contains("        1 => include_str!(\"../inputs/1.txt\"),\n        4 => include_str!(\"../inputs/4.txt\"),\n"));
+    }
+
+    #[test]
+    fn registering_twice_changes_nothing() {
+        assert_eq!(register(LIB, 3).unwrap(), LIB);
+    }
+
+    #[test]
+    fn fills_in_the_day() {
+        assert_eq!(
+            instantiate(
+                "#[doc = include_str!(\"../puzzles/N.md\")]\npub struct DayN;\n// dayN.rs\ncrate::assert_answers(N);",
+                6
+            ),
+            "#[doc = include_str!(\"../puzzles/6.md\")]\npub struct Day6;\n// day6.rs\ncrate::assert_answers(6);"
+        );
+    }
+}
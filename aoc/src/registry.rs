@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+
+use aoc_core::{answers::Answers, Entry, Result};
+
+/// The workspace root, where each year's crate lives in a directory named
+/// after the year.
+pub const WORKSPACE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+/// Every solved day across all years, in year then day order.
+pub fn entries() -> Vec<Entry> {
+    aoc_2020::solutions()
+        .into_iter()
+        .chain(aoc_2022::solutions())
+        .collect()
+}
+
+pub fn find(year: u16, day: u8) -> Option<Entry> {
+    entries()
+        .into_iter()
+        .find(|entry| entry.year == year && entry.day == day)
+}
+
+pub fn input(entry: &Entry) -> Result<String> {
+    match entry.year {
+        aoc_2020::YEAR => aoc_2020::input(entry.day),
+        _ => aoc_2022::input(entry.day),
+    }
+}
+
+pub fn answers(year: u16) -> Result<Answers> {
+    match year {
+        aoc_2020::YEAR => aoc_2020::answers(),
+        _ => aoc_2022::answers(),
+    }
+}
+
+/// Where a year's crate keeps its `N.txt` inputs.
+pub fn input_dir(year: u16) -> Option<&'static str> {
+    match year {
+        aoc_2020::YEAR => Some(aoc_2020::INPUT_DIR),
+        aoc_2022::YEAR => Some(aoc_2022::INPUT_DIR),
+        _ => None,
+    }
+}
+
+/// Where a year's crate keeps its `N.md` puzzle descriptions.
+pub fn puzzle_dir(year: u16) -> Option<&'static str> {
+    match year {
+        aoc_2020::YEAR => Some(aoc_2020::PUZZLE_DIR),
+        aoc_2022::YEAR => Some(aoc_2022::PUZZLE_DIR),
+        _ => None,
+    }
+}
+
+pub fn crate_dir(year: u16) -> PathBuf {
+    Path::new(WORKSPACE).join(year.to_string())
+}
@@ -0,0 +1,213 @@
+use std::{
+    fs,
+    io::{stdout, IsTerminal},
+    path::Path,
+    process::exit,
+};
+
+use aoc_core::Part;
+use clap::{App, Arg, ArgMatches};
+
+use crate::registry;
+
+const BOLD_UNDERLINE: &str = "\x1b[1;4m";
+const CODE: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+pub fn command() -> App<'static> {
+    App::new("show")
+        .about("Prints a day's puzzle description")
+        .arg(
+            Arg::new("year")
+                .short('y')
+                .long("year")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("day")
+                .short('d')
+                .long("day")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("part")
+                .short('p')
+                .long("part")
+                .takes_value(true)
+                .possible_values(["1", "2"]),
+        )
+}
+
+pub fn exec(matches: &ArgMatches) {
+    let year: u16 = matches.value_of_t_or_exit("year");
+    let day: u8 = matches.value_of_t_or_exit("day");
+    let dir = registry::puzzle_dir(year).unwrap_or_else(|| {
+        eprintln!("There's no crate for {}", year);
+        exit(1);
+    });
+
+    let path = Path::new(dir).join(format!("{}.md", day));
+    let description = fs::read_to_string(&path).unwrap_or_default();
+    if description.trim().is_empty() {
+        eprintln!(
+            "No description saved for {} day {}; put it in {}",
+            year,
+            day,
+            path.display()
+        );
+        exit(1);
+    }
+
+    let description = match matches.value_of("part") {
+        Some("1") => section(&description, Part::One),
+        Some(_) => section(&description, Part::Two),
+        None => &description,
+    };
+    print!("{}", render(description, stdout().is_terminal()));
+}
+
+/// The part of a description under its `# Part One` or `# Part Two`
+/// heading.
+fn section(description: &str, part: Part) -> &str {
+    match (description.find("# Part Two"), part) {
+        (Some(split), Part::One) => &description[..split],
+        (Some(split), Part::Two) => &description[split..],
+        (None, Part::One) => description,
+        (None, Part::Two) => "",
+    }
+}
+
+/// Lays markdown out for a terminal: headings stand out, list items get
+/// bullets and code blocks are indented. `styled` adds ANSI colours;
+/// without it headings are underlined with `=`/`-` instead.
+fn render(markdown: &str, styled: bool) -> String {
+    let mut output = String::new();
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+
+        if in_code {
+            match styled {
+                true => output.push_str(&format!("    {}{}{}\n", CODE, line, RESET)),
+                false => output.push_str(&format!("    {}\n", line)),
+            }
+            continue;
+        }
+
+        let level = line.chars().take_while(|&c| c == '#').count();
+        if level > 0 && line[level..].starts_with(' ') {
+            let heading = line[level..].trim();
+            match styled {
+                true => output.push_str(&format!("{}{}{}\n", BOLD_UNDERLINE, heading, RESET)),
+                false => {
+                    let rule = if level == 1 { "=" } else { "-" };
+                    output.push_str(&format!(
+                        "{}\n{}\n",
+                        heading,
+                        rule.repeat(heading.chars().count())
+                    ));
+                }
+            }
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            let bullet = if indent == 0 { '•' } else { '◦' };
+            output.push_str(&format!(
+                "{}  {} {}\n",
+                " ".repeat(indent),
+                bullet,
+                inline(item, styled)
+            ));
+            continue;
+        }
+
+        output.push_str(&inline(line, styled));
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Colours `code` spans when styling; leaves the backticks otherwise.
+fn inline(text: &str, styled: bool) -> String {
+    if !styled {
+        return text.to_string();
+    }
+
+    text.split('`')
+        .enumerate()
+        .map(|(index, piece)| match index % 2 {
+            1 => format!("{}{}{}", CODE, piece, RESET),
+            _ => piece.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, section};
+    use aoc_core::Part;
+
+    const DESCRIPTION: &str = "# Part One
+
+For example:
+
+- 1721
+    - nested
+- 979
+
+```text
+..#
+```
+
+# Part Two
+
+Use `979` again.
+";
+
+    #[test]
+    fn renders_plain() {
+        assert_eq!(
+            render(DESCRIPTION, false),
+            "Part One
+========
+
+For example:
+
+  • 1721
+      ◦ nested
+  • 979
+
+    ..#
+
+Part Two
+========
+
+Use `979` again.
+"
+        );
+    }
+
+    #[test]
+    fn renders_styled() {
+        let rendered = render(DESCRIPTION, true);
+        assert!(rendered.starts_with("\x1b[1;4mPart One\x1b[0m\n"));
+        assert!(rendered.contains("    \x1b[36m..#\x1b[0m\n"));
+        assert!(rendered.contains("Use \x1b[36m979\x1b[0m again."));
+    }
+
+    #[test]
+    fn splits_parts() {
+        assert!(section(DESCRIPTION, Part::One).ends_with("```\n\n"));
+        assert!(section(DESCRIPTION, Part::Two).starts_with("# Part Two"));
+        assert_eq!(section("# Part One\n", Part::Two), "");
+    }
+}
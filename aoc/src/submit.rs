@@ -0,0 +1,297 @@
+use std::{process::exit, time::Duration};
+
+use aoc_core::{
+    runner,
+    submissions::{self, Outcome, Submission},
+    Part, Result,
+};
+use clap::{App, Arg, ArgMatches};
+
+use crate::{client::Client, config, registry};
+
+pub fn command() -> App<'static> {
+    App::new("submit")
+        .about("Submits an answer and records what the site made of it")
+        .arg(
+            Arg::new("year")
+                .short('y')
+                .long("year")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("day")
+                .short('d')
+                .long("day")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("part")
+                .short('p')
+                .long("part")
+                .required(true)
+                .takes_value(true)
+                .possible_values(["1", "2"]),
+        )
+        .arg(Arg::new("answer").help("Answer to submit; defaults to running the solution for it"))
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .help("Submit even if the answer is already known to be wrong"),
+        )
+        .arg(
+            Arg::new("record")
+                .long("record")
+                .takes_value(true)
+                .possible_values(["correct", "too-high", "too-low", "wrong"])
+                .requires("answer")
+                .help("Log the outcome of an answer submitted elsewhere instead of posting it"),
+        )
+}
+
+pub fn exec(matches: &ArgMatches) {
+    let year: u16 = matches.value_of_t_or_exit("year");
+    let day: u8 = matches.value_of_t_or_exit("day");
+    let part = match matches.value_of("part") {
+        Some("1") => Part::One,
+        _ => Part::Two,
+    };
+
+    let history = submissions::load().unwrap_or_else(|error| {
+        eprintln!("Couldn't load past submissions: {}", error);
+        exit(1);
+    });
+
+    if let Some(outcome) = matches.value_of("record") {
+        let outcome = match outcome {
+            "correct" => Outcome::Correct,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            _ => Outcome::Wrong,
+        };
+        let answer = matches.value_of("answer").unwrap_or_default();
+        let submission = Submission::new(year, day, part, answer, outcome, None);
+        if let Err(error) = submissions::record(&submission) {
+            eprintln!("Couldn't record submission: {}", error);
+            exit(1);
+        }
+        println!(
+            "Recorded {} day {} part {}: {} is {}",
+            year, day, part, answer, submission.outcome
+        );
+        return;
+    }
+
+    if let Some(wait) = submissions::cooldown(&history, year, day, part, submissions::now()) {
+        eprintln!(
+            "Still cooling down from the last answer; try again in {}s",
+            wait.as_secs()
+        );
+        exit(1);
+    }
+
+    let answer = match matches.value_of("answer") {
+        Some(answer) => answer.to_string(),
+        None => solve(year, day, part).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            exit(1);
+        }),
+    };
+
+    if let Some(warning) = submissions::flag(&history, year, day, part, &answer) {
+        if !matches.is_present("force") {
+            eprintln!("Not submitting: {} (--force to submit anyway)", warning);
+            exit(1);
+        }
+    }
+
+    let submission = config::load()
+        .and_then(|config| Client::new(&config))
+        .and_then(|client| submit(&client, year, day, part, &answer));
+    let submission = match submission {
+        Ok(submission) => submission,
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    };
+
+    if let Err(error) = submissions::record(&submission) {
+        eprintln!("warning: couldn't record submission: {}", error);
+    }
+    println!(
+        "{} day {} part {}: {} is {}",
+        year, day, part, answer, submission.outcome
+    );
+    if let Some(until) = submission.cooldown_until {
+        println!("Next answer accepted in {}s", until - submission.timestamp);
+    }
+
+    exit(if submission.outcome == Outcome::Correct {
+        0
+    } else {
+        1
+    })
+}
+
+fn solve(year: u16, day: u8, part: Part) -> Result<String> {
+    let entry =
+        registry::find(year, day).ok_or_else(|| format!("{} day {} isn't solved", year, day))?;
+    let input = registry::input(&entry)?;
+    let record = runner::run(&entry, &input, &[part]).remove(0);
+    match (record.answer, record.error) {
+        (Some(answer), _) => Ok(answer),
+        (None, error) => Err(From::from(error.unwrap_or_default())),
+    }
+}
+
+pub fn submit(client: &Client, year: u16, day: u8, part: Part, answer: &str) -> Result<Submission> {
+    let page = client.post(
+        &format!("/{}/day/{}/answer", year, day),
+        &[("level", &part.to_string()), ("answer", answer)],
+    )?;
+    let (outcome, wait) = parse_response(&page)?;
+    Ok(Submission::new(year, day, part, answer, outcome, wait))
+}
+
+/// Reads the verdict, and any wait before the next attempt, out of the page
+/// the site returns for a submitted answer.
+pub fn parse_response(page: &str) -> Result<(Outcome, Option<Duration>)> {
+    let page = page.to_lowercase();
+
+    if page.contains("you gave an answer too recently") {
+        let wait = between(&page, "you have ", " left to wait")
+            .and_then(parse_wait)
+            .ok_or("Rate limited, but couldn't tell for how long")?;
+        return Ok((Outcome::RateLimited { wait }, Some(wait)));
+    }
+    if page.contains("that's the right answer") {
+        return Ok((Outcome::Correct, None));
+    }
+    if page.contains("you don't seem to be solving the right level") {
+        return Err(From::from(
+            "The site isn't expecting this part; is it already solved, or part 1 not yet?",
+        ));
+    }
+
+    let outcome = if page.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if page.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if page.contains("that's not the right answer") {
+        Outcome::Wrong
+    } else {
+        return Err(From::from(
+            "Couldn't make sense of the response to the answer",
+        ));
+    };
+    let wait = between(&page, "please wait ", " before trying again").and_then(parse_wait);
+    Ok((outcome, wait))
+}
+
+/// The text between `end` and the nearest `start` before it.
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let before = &text[..text.find(end)?];
+    Some(&before[before.rfind(start)? + start.len()..])
+}
+
+/// Understands both `1m 5s` and `one minute`/`5 minutes`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if let [count, unit] = words[..] {
+        if unit.starts_with("minute") {
+            let count = if count == "one" {
+                1
+            } else {
+                count.parse().ok()?
+            };
+            return Some(Duration::from_secs(count * 60));
+        }
+    }
+
+    words.iter().try_fold(Duration::ZERO, |total, word| {
+        let (count, unit) = word.split_at(word.len().checked_sub(1)?);
+        let count: u64 = count.parse().ok()?;
+        let seconds = match unit {
+            "h" => count * 3600,
+            "m" => count * 60,
+            "s" => count,
+            _ => return None,
+        };
+        Some(total + Duration::from_secs(seconds))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_core::{submissions::Outcome, Part};
+
+    use super::{parse_response, submit};
+    use crate::client::stand_in::{client, scratch, serve};
+
+    #[test]
+    fn recognizes_each_outcome() {
+        let cases = [
+            (
+                "<article><p>That's the right answer!  You are one gold star closer.</p></article>",
+                Outcome::Correct,
+                None,
+            ),
+            (
+                "<article><p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p></article>",
+                Outcome::TooHigh,
+                Some(60),
+            ),
+            (
+                "<article><p>That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.</p></article>",
+                Outcome::TooLow,
+                Some(300),
+            ),
+            (
+                "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>",
+                Outcome::Wrong,
+                None,
+            ),
+            (
+                "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>",
+                Outcome::RateLimited {
+                    wait: Duration::from_secs(65),
+                },
+                Some(65),
+            ),
+        ];
+
+        for (page, outcome, wait) in cases {
+            assert_eq!(
+                parse_response(page).unwrap(),
+                (outcome, wait.map(Duration::from_secs))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unexpected_pages() {
+        assert!(parse_response("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>").is_err());
+        assert!(parse_response("<html>maintenance</html>").is_err());
+    }
+
+    #[test]
+    fn posts_the_answer() {
+        let (base_url, requests) = serve(vec![(
+            200,
+            "<article><p>That's the right answer!</p></article>",
+        )]);
+        let client = client(&base_url, Duration::ZERO, &scratch("submit"));
+
+        let submission = submit(&client, 2022, 5, Part::Two, "BRQWDBBJM").unwrap();
+        assert_eq!(submission.outcome, Outcome::Correct);
+        assert_eq!(submission.cooldown_until, None);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2022/day/5/answer "));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=BRQWDBBJM"));
+    }
+}
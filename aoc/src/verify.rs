@@ -0,0 +1,76 @@
+use std::process::exit;
+
+use aoc_core::{
+    answers::{self, Verdict},
+    runner::{self, Status},
+    Part,
+};
+use clap::{App, Arg, ArgMatches};
+
+use crate::registry;
+
+pub fn command() -> App<'static> {
+    App::new("verify")
+        .about("Checks every day's answers against the years' answers.toml")
+        .arg(Arg::new("year").short('y').long("year").takes_value(true))
+        .arg(Arg::new("day").short('d').long("day").takes_value(true))
+}
+
+pub fn exec(matches: &ArgMatches) {
+    let year = matches.value_of("year");
+    let day = matches.value_of("day");
+    let entries: Vec<_> = registry::entries()
+        .into_iter()
+        .filter(|entry| year.is_none_or(|year| entry.year.to_string() == year))
+        .filter(|entry| day.is_none_or(|day| entry.day.to_string() == day))
+        .collect();
+
+    if entries.is_empty() {
+        eprintln!("No solved days match");
+        exit(1);
+    }
+
+    println!(
+        "{:>4}  {:>3}  {:>4}  {:<7}  Answer",
+        "Year", "Day", "Part", "Verdict"
+    );
+    let mut failed = false;
+    for entry in &entries {
+        let answers = match registry::answers(entry.year) {
+            Ok(answers) => answers,
+            Err(error) => {
+                eprintln!("{}", error);
+                exit(1);
+            }
+        };
+
+        let records = match registry::input(entry) {
+            Ok(input) => runner::run(entry, &input, &[Part::One, Part::Two]),
+            Err(error) => runner::failed(entry, &[Part::One, Part::Two], &*error),
+        };
+        for record in records {
+            let verdict =
+                answers::check(&answers, record.day, record.part, record.answer.as_deref());
+            let detail = match (&verdict, &record.answer, &record.error) {
+                (Verdict::Fail { expected }, Some(answer), _) => {
+                    format!("{} (expected {})", answer, expected)
+                }
+                (Verdict::Fail { expected }, None, error) => format!(
+                    "{} (expected {})",
+                    error.as_deref().unwrap_or("no answer"),
+                    expected
+                ),
+                (_, Some(answer), _) => answer.clone(),
+                (_, None, error) => error.clone().unwrap_or_default(),
+            };
+            failed |= record.status != Status::Ok
+                || matches!(verdict, Verdict::Fail { .. } | Verdict::Error);
+            println!(
+                "{:>4}  {:>3}  {:>4}  {:<7}  {}",
+                record.year, record.day, record.part, verdict, detail
+            );
+        }
+    }
+
+    exit(if failed { 1 } else { 0 })
+}
@@ -0,0 +1,250 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{exit, Command},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use aoc_core::{input, Result};
+use clap::{App, Arg, ArgMatches};
+use serde::Deserialize;
+
+use crate::registry;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+pub fn command() -> App<'static> {
+    App::new("watch")
+        .about("Rebuilds and reruns a day whenever its source or input changes")
+        .arg(
+            Arg::new("year")
+                .short('y')
+                .long("year")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("day")
+                .short('d')
+                .long("day")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("release")
+                .long("release")
+                .help("Build with optimizations, for days that are slow in debug"),
+        )
+}
+
+/// One line of the runner's `--format json` output.
+#[derive(Clone, Debug, Deserialize)]
+struct Run {
+    part: u8,
+    answer: Option<String>,
+    elapsed_us: u64,
+    error: Option<String>,
+    warning: Option<String>,
+}
+
+pub fn exec(matches: &ArgMatches) {
+    let year: u16 = matches.value_of_t_or_exit("year");
+    let day: u8 = matches.value_of_t_or_exit("day");
+    let release = matches.is_present("release");
+    let input_dir = registry::input_dir(year).unwrap_or_else(|| {
+        eprintln!("There's no crate for {}", year);
+        exit(1);
+    });
+
+    let paths = vec![
+        registry::crate_dir(year)
+            .join("src")
+            .join(format!("day{}.rs", day)),
+        input::default_path(input_dir, day),
+    ];
+
+    let mut stamps = modified(&paths);
+    let mut previous: Option<Vec<Run>> = None;
+    loop {
+        let body = match rebuild_and_run(year, day, release) {
+            Ok(runs) => {
+                let body = draw(&runs, previous.as_deref());
+                previous = Some(runs);
+                body
+            }
+            Err(error) => format!("{}\n", error),
+        };
+
+        print!("\x1b[2J\x1b[H");
+        println!("Watching {} day {}; Ctrl-C to stop", year, day);
+        for path in &paths {
+            println!("  {}", path.display());
+        }
+        println!();
+        print!("{}", body);
+
+        while modified(&paths) == stamps {
+            thread::sleep(POLL_INTERVAL);
+        }
+        stamps = modified(&paths);
+    }
+}
+
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
+
+/// Builds the runner and has it solve both parts, reporting build errors
+/// verbatim.
+fn rebuild_and_run(year: u16, day: u8, release: bool) -> Result<Vec<Run>> {
+    let cargo = |subcommand: &str| {
+        let mut command = Command::new(env!("CARGO"));
+        command
+            .current_dir(registry::WORKSPACE)
+            .args([subcommand, "--quiet", "--package", "aoc"]);
+        if release {
+            command.arg("--release");
+        }
+        command
+    };
+
+    let build = cargo("build").output()?;
+    if !build.status.success() {
+        return Err(From::from(format!(
+            "Build failed:\n\n{}",
+            String::from_utf8_lossy(&build.stderr)
+        )));
+    }
+
+    let run = cargo("run")
+        .args(["--", "--year", &year.to_string(), "--day", &day.to_string()])
+        .args(["--format", "json"])
+        .output()?;
+    let runs: Vec<Run> = String::from_utf8_lossy(&run.stdout)
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<_, _>>()?;
+
+    if runs.is_empty() {
+        return Err(From::from(format!(
+            "Run failed:\n\n{}",
+            String::from_utf8_lossy(&run.stderr)
+        )));
+    }
+    Ok(runs)
+}
+
+/// A table of this run's answers and timings, each compared with the run
+/// before it.
+fn draw(runs: &[Run], previous: Option<&[Run]>) -> String {
+    let mut screen = format!(
+        "{:>4}  {:<20}  {:>10}  {}\n",
+        "Part", "Answer", "Time", "Since last run"
+    );
+
+    for run in runs {
+        let before = previous
+            .into_iter()
+            .flatten()
+            .find(|before| before.part == run.part);
+        let elapsed = Duration::from_micros(run.elapsed_us);
+
+        let (answer, time) = match (&run.answer, &run.error) {
+            (Some(answer), _) => (answer.clone(), format!("{:.2?}", elapsed)),
+            (None, error) => (
+                format!("error: {}", error.as_deref().unwrap_or_default()),
+                String::new(),
+            ),
+        };
+
+        let row = format!(
+            "{:>4}  {:<20}  {:>10}  {}",
+            run.part,
+            answer,
+            time,
+            diff(before, run)
+        );
+        screen.push_str(row.trim_end());
+        screen.push('\n');
+        if let Some(warning) = &run.warning {
+            screen.push_str(&format!("{:>4}  !!! KNOWN WRONG: {}\n", "", warning));
+        }
+    }
+
+    screen
+}
+
+fn diff(before: Option<&Run>, run: &Run) -> String {
+    let before = match before {
+        Some(before) => before,
+        None => return String::new(),
+    };
+
+    let answer = if before.answer == run.answer {
+        "same answer".to_string()
+    } else {
+        format!("was {}", before.answer.as_deref().unwrap_or("an error"))
+    };
+
+    match (&before.answer, &run.answer, before.elapsed_us) {
+        (Some(_), Some(_), previous) if previous > 0 => {
+            let change = (run.elapsed_us as f64 - previous as f64) / previous as f64 * 100.0;
+            format!("{}, {:+.0}% time", answer, change)
+        }
+        _ => answer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{draw, Run};
+
+    fn run(part: u8, answer: Option<&str>, elapsed_us: u64) -> Run {
+        Run {
+            part,
+            answer: answer.map(String::from),
+            elapsed_us,
+            error: answer.is_none().then(|| "parse error".to_string()),
+            warning: None,
+        }
+    }
+
+    #[test]
+    fn first_run_has_nothing_to_compare() {
+        assert_eq!(
+            draw(&[run(1, Some("66186"), 1500)], None),
+            concat!(
+                "Part  Answer                      Time  Since last run\n",
+                "   1  66186                     1.50ms\n"
+            )
+        );
+    }
+
+    #[test]
+    fn compares_with_previous_run() {
+        let previous = [run(1, Some("66186"), 1000), run(2, None, 0)];
+        let current = [run(1, Some("66186"), 1500), run(2, Some("196804"), 20)];
+        let screen = draw(&current, Some(&previous));
+
+        assert!(screen.contains("   1  66186                     1.50ms  same answer, +50% time\n"));
+        assert!(screen.contains("   2  196804                   20.00µs  was an error\n"));
+    }
+
+    #[test]
+    fn shows_errors_and_warnings() {
+        let mut known_wrong = run(1, Some("1"), 10);
+        known_wrong.warning = Some("1 is too low; 5 already was".to_string());
+        let screen = draw(&[known_wrong, run(2, None, 0)], None);
+
+        assert!(screen.contains("      !!! KNOWN WRONG: 1 is too low; 5 already was\n"));
+        assert!(screen.contains("   2  error: parse error"));
+    }
+}
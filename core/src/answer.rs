@@ -0,0 +1,219 @@
+use std::fmt::{self, Display};
+
+/// How tall the block letters some puzzles draw their answers in are.
+const LETTER_HEIGHT: usize = 6;
+/// How wide each block letter is, not counting the blank column after it.
+const LETTER_WIDTH: usize = 4;
+
+/// The block letters puzzles draw, `#` lit and `.` dark.
+const ALPHABET: &[(char, [&str; LETTER_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// What a part of a day comes to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    /// A `u128` too big for [`Answer::Integer`]. Smaller ones are converted
+    /// to that instead, so equal numbers compare equal.
+    Unsigned(u128),
+    Text(String),
+    /// A picture, usually of letters, that reads as text when
+    /// [`Pixels::read`] can make them out.
+    Pixels(Pixels),
+}
+
+impl Display for Answer {
+    /// What would be submitted. Pixels that don't read as letters are drawn
+    /// instead, on the lines after.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(number) => write!(f, "{}", number),
+            Answer::Unsigned(number) => write!(f, "{}", number),
+            Answer::Text(text) => f.write_str(text),
+            Answer::Pixels(pixels) => match pixels.read() {
+                Some(letters) => f.write_str(&letters),
+                None => write!(f, "\n{}", pixels),
+            },
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(answer: $t) -> Self {
+                Answer::Integer(answer as i128)
+            }
+        })*
+    };
+}
+
+answer_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<i128> for Answer {
+    fn from(answer: i128) -> Self {
+        Answer::Integer(answer)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(answer: u128) -> Self {
+        match i128::try_from(answer) {
+            Ok(answer) => Answer::Integer(answer),
+            Err(_) => Answer::Unsigned(answer),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(answer: String) -> Self {
+        Answer::Text(answer)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(answer: &str) -> Self {
+        Answer::Text(answer.to_string())
+    }
+}
+
+impl From<Pixels> for Answer {
+    fn from(answer: Pixels) -> Self {
+        Answer::Pixels(answer)
+    }
+}
+
+/// A grid of lit and dark pixels, top row first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pixels {
+    pub rows: Vec<Vec<bool>>,
+}
+
+impl Pixels {
+    /// Reads a drawing where `#` is lit and anything else is dark.
+    pub fn from_drawing(drawing: &str) -> Self {
+        Pixels {
+            rows: drawing
+                .lines()
+                .map(|line| line.chars().map(|pixel| pixel == '#').collect())
+                .collect(),
+        }
+    }
+
+    /// The letters the pixels spell, when they're a row of the puzzles'
+    /// 4×6 block letters with a blank column between each.
+    pub fn read(&self) -> Option<String> {
+        if self.rows.len() != LETTER_HEIGHT {
+            return None;
+        }
+        let lit = |row: usize, column: usize| self.rows[row].get(column) == Some(&true);
+        let width = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let letters = (width + 1) / (LETTER_WIDTH + 1);
+        let stray = |row: &Vec<bool>| {
+            row.iter()
+                .skip(letters * (LETTER_WIDTH + 1))
+                .any(|&lit| lit)
+        };
+        if letters == 0 || self.rows.iter().any(stray) {
+            return None;
+        }
+
+        (0..letters)
+            .map(|letter| {
+                let left = letter * (LETTER_WIDTH + 1);
+                if (0..LETTER_HEIGHT).any(|row| lit(row, left + LETTER_WIDTH)) {
+                    return None;
+                }
+                ALPHABET
+                    .iter()
+                    .find(|(_, glyph)| {
+                        glyph.iter().enumerate().all(|(row, pixels)| {
+                            pixels
+                                .chars()
+                                .enumerate()
+                                .all(|(column, pixel)| lit(row, left + column) == (pixel == '#'))
+                        })
+                    })
+                    .map(|&(letter, _)| letter)
+            })
+            .collect()
+    }
+}
+
+impl Display for Pixels {
+    /// The pixels as `#` and `.`, a line to each row.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect();
+        f.write_str(&rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answer, Pixels};
+
+    const HELLO: &str = "\
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.";
+
+    #[test]
+    fn integers_go_up_to_128_bits() {
+        assert_eq!(Answer::from(7u32).to_string(), "7");
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(
+            Answer::from(i128::MAX).to_string(),
+            "170141183460469231731687303715884105727"
+        );
+        assert_eq!(Answer::from(5u128), Answer::Integer(5));
+        assert_eq!(
+            Answer::from(i128::MAX as u128 + 1).to_string(),
+            "170141183460469231731687303715884105728"
+        );
+        assert_eq!(
+            Answer::from(u128::MAX).to_string(),
+            "340282366920938463463374607431768211455"
+        );
+    }
+
+    #[test]
+    fn reads_block_letters() {
+        let pixels = Pixels::from_drawing(HELLO);
+        assert_eq!(pixels.read().as_deref(), Some("HELLO"));
+        assert_eq!(Answer::from(pixels).to_string(), "HELLO");
+    }
+
+    #[test]
+    fn draws_pixels_it_cannot_read() {
+        let smudged = HELLO.replacen('.', "#", 1);
+        let pixels = Pixels::from_drawing(&smudged);
+
+        assert_eq!(pixels.read(), None);
+        assert_eq!(pixels.to_string(), smudged);
+        assert_eq!(Answer::from(pixels).to_string(), format!("\n{}", smudged));
+    }
+}
@@ -0,0 +1,203 @@
+use std::{collections::BTreeMap, fmt::Display, fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    input,
+    runner::{self, Status},
+    Entry, Part, Result,
+};
+
+/// The accepted answers for one year, as kept in its `answers.toml`:
+///
+/// ```toml
+/// [day1]
+/// part1 = "66186"
+/// part2 = "196804"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    days: BTreeMap<u8, DayAnswers>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(contents: &str) -> Result<Self> {
+        let tables: BTreeMap<String, DayAnswers> = toml::from_str(contents)?;
+        let days = tables
+            .into_iter()
+            .map(|(key, answers)| {
+                let day = key
+                    .strip_prefix("day")
+                    .and_then(|day| day.parse().ok())
+                    .ok_or_else(|| {
+                        format!("Answers table `{}` should be named like `day1`", key)
+                    })?;
+                Ok((day, answers))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Answers { days })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Couldn't read answers `{}`: {}", path.display(), error))?;
+        Answers::parse(&contents)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        let answers = self.days.get(&day)?;
+        match part {
+            Part::One => answers.part1.as_deref(),
+            Part::Two => answers.part2.as_deref(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// No answer came out, and none is on record to expect either.
+    Error,
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Error => "error",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+pub fn check(answers: &Answers, day: u8, part: Part, answer: Option<&str>) -> Verdict {
+    match (answers.get(day, part), answer) {
+        (None, None) => Verdict::Error,
+        (None, Some(_)) => Verdict::Unknown,
+        (Some(expected), Some(answer)) if expected == answer => Verdict::Pass,
+        (Some(expected), _) => Verdict::Fail {
+            expected: expected.to_string(),
+        },
+    }
+}
+
+/// Runs both parts of `entry` and panics if either fails or gives an answer
+/// other than the one on record. Meant for the per-day `it_works` tests.
+pub fn assert_day(entry: &Entry, input: &str, answers: &Answers) {
+    assert_runs(entry, input, answers, "");
+}
+
+/// Like [`assert_day`], but against every [`variant`](input::variants) of
+/// `input`, to catch a day that only copes with the file exactly as saved.
+pub fn assert_variants(entry: &Entry, input: &str, answers: &Answers) {
+    for (variant, text) in input::variants(input) {
+        assert_runs(entry, &text, answers, &format!(" with {}", variant));
+    }
+}
+
+/// Looks `day` up among a year's `solutions` and holds it to `assert`
+/// ([`assert_day`] or [`assert_variants`]) with the input `input` loads for
+/// it. What each year's `assert_answers` and `assert_variants` come down to.
+pub fn assert_solution(
+    assert: fn(&Entry, &str, &Answers),
+    solutions: Vec<Entry>,
+    input: impl FnOnce(u8) -> Result<String>,
+    answers: Result<Answers>,
+    day: u8,
+) {
+    let entry = solutions
+        .into_iter()
+        .find(|entry| entry.day == day)
+        .expect("day is not registered");
+    assert(&entry, &input(day).unwrap(), &answers.unwrap());
+}
+
+fn assert_runs(entry: &Entry, input: &str, answers: &Answers, context: &str) {
+    for record in runner::run(entry, input, &[Part::One, Part::Two]) {
+        let run = format!(
+            "{} day {} part {}{}",
+            record.year, record.day, record.part, context
+        );
+        if record.status != Status::Ok {
+            panic!(
+                "{} failed: {}",
+                run,
+                record.error.as_deref().unwrap_or_default()
+            );
+        }
+
+        match answers.get(record.day, record.part) {
+            Some(expected) => assert_eq!(record.answer.as_deref(), Some(expected), "{}", run),
+            None => eprintln!(
+                "{} has no answer on record to check {} against",
+                run,
+                record.answer.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_day, check, Answers, Verdict};
+    use crate::{Entry, Input, Part, Result, Solution};
+
+    struct Unfinished;
+
+    impl Solution for Unfinished {
+        type Parsed = ();
+        type Answer = u32;
+
+        fn parse(_input: &Input) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> Result<u32> {
+            Ok(1)
+        }
+
+        fn part2(_: &()) -> Result<u32> {
+            todo!()
+        }
+    }
+
+    #[test]
+    fn parses_and_checks() {
+        let answers =
+            Answers::parse("[day1]\npart1 = \"514579\"\n\n[day2]\npart2 = \"1\"\n").unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("514579"));
+        assert_eq!(answers.get(1, Part::Two), None);
+        assert_eq!(check(&answers, 1, Part::One, Some("514579")), Verdict::Pass);
+        assert_eq!(
+            check(&answers, 2, Part::Two, Some("2")),
+            Verdict::Fail {
+                expected: "1".to_string()
+            }
+        );
+        assert_eq!(check(&answers, 3, Part::One, Some("2")), Verdict::Unknown);
+        assert_eq!(check(&answers, 3, Part::One, None), Verdict::Error);
+    }
+
+    #[test]
+    fn rejects_unrecognized_tables() {
+        assert!(Answers::parse("[one]\npart1 = \"1\"\n").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "0 day 0 part 2 failed")]
+    fn fails_days_that_fail_even_without_answers() {
+        assert_day(&Entry::new::<Unfinished>(0, 0), "", &Answers::default());
+    }
+}
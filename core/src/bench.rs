@@ -0,0 +1,327 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    history,
+    report::{Format, Row},
+    runner::guard,
+    Entry, Input, Part, Result,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    /// Untimed runs before sampling starts, to settle caches and allocators.
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            warmup: 10,
+            iterations: 100,
+        }
+    }
+}
+
+/// What was timed: parsing the input, or solving one part against an
+/// already-parsed model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => f.pad("parse"),
+            Stage::Solve(part) => f.pad(&format!("part{}", part)),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Solve(Part::One)),
+            "part2" => Ok(Stage::Solve(Part::Two)),
+            _ => Err(format!("Stage `{}` not recognized", s)),
+        }
+    }
+}
+
+impl Serialize for Stage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Stage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: usize,
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", with = "nanos")]
+    pub mean: Duration,
+    #[serde(rename = "stddev_ns", with = "nanos")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len().max(1) as f64;
+        let median = match sorted.len() {
+            0 => Duration::ZERO,
+            len if len % 2 == 1 => sorted[len / 2],
+            len => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+        };
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / n;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1.0).max(1.0);
+
+        Stats {
+            samples: sorted.len(),
+            min: sorted.first().copied().unwrap_or_default(),
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_nanos(u64::deserialize(deserializer)?))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+pub const HUMAN_HEADER: &str = "Day  Stage          Min       Median         Mean       Stddev";
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Stats {
+            min,
+            median,
+            mean,
+            stddev,
+            ..
+        } = self.stats;
+        write!(
+            f,
+            "{:>3}  {:<5}  {:>11}  {:>11}  {:>11}  {:>11}",
+            self.day,
+            self.stage,
+            format!("{:.2?}", min),
+            format!("{:.2?}", median),
+            format!("{:.2?}", mean),
+            format!("{:.2?}", stddev)
+        )
+    }
+}
+
+impl Row for Measurement {
+    const TSV_HEADER: &'static str =
+        "year\tday\tstage\tsamples\tmin_ns\tmedian_ns\tmean_ns\tstddev_ns";
+
+    fn tsv_fields(&self) -> Vec<String> {
+        vec![
+            self.year.to_string(),
+            self.day.to_string(),
+            self.stage.to_string(),
+            self.stats.samples.to_string(),
+            self.stats.min.as_nanos().to_string(),
+            self.stats.median.as_nanos().to_string(),
+            self.stats.mean.as_nanos().to_string(),
+            self.stats.stddev.as_nanos().to_string(),
+        ]
+    }
+}
+
+pub fn print_header(format: Format) {
+    match format {
+        Format::Human => println!("{}", HUMAN_HEADER),
+        _ => {
+            if let Some(header) = format.header::<Measurement>() {
+                println!("{}", header);
+            }
+        }
+    }
+}
+
+pub fn print(format: Format, measurements: &[Measurement]) {
+    for measurement in measurements {
+        match format.line(measurement) {
+            Some(line) => println!("{}", line),
+            None => println!("{}", measurement),
+        }
+    }
+}
+
+/// Times `f` over and over. A panic in it comes out as an error, so one
+/// broken day doesn't stop a whole run of benchmarks.
+fn sample<T>(options: &Options, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    let mut f = || guard(&mut f);
+    for _ in 0..options.warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::with_capacity(options.iterations);
+    for _ in 0..options.iterations {
+        let start = Instant::now();
+        let result = f()?;
+        samples.push(start.elapsed());
+        // Dropping the result is the caller's cost, not the solution's.
+        drop(result);
+    }
+
+    Ok(Stats::from_samples(&samples))
+}
+
+/// Times parsing `input`, then each of `parts` against a single parsed
+/// model, so the two costs are reported separately.
+pub fn bench(
+    entry: &Entry,
+    input: &str,
+    parts: &[Part],
+    options: &Options,
+) -> Result<Vec<Measurement>> {
+    let measurement = |stage, stats| Measurement {
+        year: entry.year,
+        day: entry.day,
+        stage,
+        stats,
+    };
+
+    let input = Input::new(input);
+    let mut measurements = vec![measurement(
+        Stage::Parse,
+        sample(options, || entry.parse(&input))?,
+    )];
+
+    let parsed = guard(|| entry.parse(&input))?;
+    for &part in parts {
+        let stats = sample(options, || parsed.solve(&part))?;
+        measurements.push(measurement(Stage::Solve(part), stats));
+    }
+
+    Ok(measurements)
+}
+
+/// `bench`, with the measurements also appended to the benchmark history so
+/// later runs can be compared against them.
+pub fn bench_and_record(
+    entry: &Entry,
+    input: &str,
+    parts: &[Part],
+    options: &Options,
+) -> Result<Vec<Measurement>> {
+    let measurements = bench(entry, input, parts, options)?;
+    if let Err(error) = history::record(&measurements, input) {
+        eprintln!("Couldn't record benchmark history: {}", error);
+    }
+    Ok(measurements)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{bench, Options, Stats};
+    use crate::{Entry, Input, Part, Result, Solution};
+
+    struct Broken;
+
+    impl Solution for Broken {
+        type Parsed = ();
+        type Answer = u32;
+
+        fn parse(_input: &Input) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> Result<u32> {
+            panic!("off by one")
+        }
+
+        fn part2(_: &()) -> Result<u32> {
+            Ok(2)
+        }
+    }
+
+    #[test]
+    fn panics_come_out_as_errors() {
+        let options = Options {
+            warmup: 0,
+            iterations: 1,
+        };
+        let error = bench(&Entry::new::<Broken>(0, 0), "", &[Part::One], &options)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("off by one"), "{}", error);
+    }
+
+    #[test]
+    fn summary_statistics() {
+        let samples = [2, 4, 4, 4, 5, 5, 7, 9].map(Duration::from_micros);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.min, Duration::from_micros(2));
+        assert_eq!(
+            stats.median,
+            Duration::from_micros(4) + Duration::from_nanos(500)
+        );
+        assert_eq!(stats.mean, Duration::from_micros(5));
+        assert_eq!(stats.stddev.as_nanos(), 2138);
+    }
+
+    #[test]
+    fn odd_sample_counts_take_the_middle() {
+        let samples = [9, 1, 5].map(Duration::from_micros);
+        assert_eq!(
+            Stats::from_samples(&samples).median,
+            Duration::from_micros(5)
+        );
+    }
+}
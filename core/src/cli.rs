@@ -0,0 +1,195 @@
+//! The flags every runner takes, and what they come to once parsed, so a new
+//! one goes in here rather than into each runner's `main`.
+
+use std::time::Duration;
+
+use clap::{Arg, ArgMatches};
+
+use crate::{
+    bench,
+    sandbox::Limits,
+    trace::{self, Level},
+    visual::{self, Playback},
+};
+
+/// `--explain` and `--verbose`. A runner whose `-v` already means something
+/// else passes `short_verbose: false` and keeps verbosity to the long name.
+pub fn trace_args(short_verbose: bool) -> Vec<Arg<'static>> {
+    let verbose = Arg::new("verbose")
+        .long("verbose")
+        .multiple_occurrences(true);
+    let verbose = match short_verbose {
+        true => verbose.short('v').help(
+            "Show more of each solution's working: -v explains, -vv adds its steps, -vvv everything",
+        ),
+        false => verbose.help(
+            "Show more of each solution's working: once explains, twice adds its steps, three times everything",
+        ),
+    };
+
+    vec![
+        Arg::new("explain")
+            .long("explain")
+            .help("Show how each answer came about"),
+        verbose,
+    ]
+}
+
+/// Sets the trace level [`trace_args`] asked for.
+pub fn set_trace_level(matches: &ArgMatches) {
+    trace::set_level(Level::from_flags(
+        matches.is_present("explain"),
+        matches.occurrences_of("verbose"),
+    ));
+}
+
+/// `--visualize` and how fast it plays.
+pub fn visual_args() -> Vec<Arg<'static>> {
+    vec![
+        Arg::new("visualize")
+            .long("visualize")
+            .help("Animate what each solution draws of its working"),
+        Arg::new("fps")
+            .long("fps")
+            .takes_value(true)
+            .requires("visualize")
+            .default_value("10")
+            .help("Frames a second to animate at"),
+        Arg::new("step")
+            .long("step")
+            .requires("visualize")
+            .help("Step through the frames one Enter at a time instead"),
+    ]
+}
+
+/// How [`visual_args`] asked for frames to be played, or `None` without
+/// `--visualize`. Turns drawing on to match.
+pub fn playback(matches: &ArgMatches) -> Option<Playback> {
+    let playback = match matches.is_present("visualize") {
+        false => None,
+        true if matches.is_present("step") => Some(Playback::Step),
+        true => Some(Playback::Fps(matches.value_of_t_or_exit("fps"))),
+    };
+    visual::set_enabled(playback.is_some());
+    playback
+}
+
+/// `--bench` and how many runs it makes.
+pub fn bench_args() -> Vec<Arg<'static>> {
+    vec![
+        Arg::new("bench")
+            .long("bench")
+            .help("Time parsing and solving over many iterations"),
+        Arg::new("warmup")
+            .long("warmup")
+            .takes_value(true)
+            .requires("bench")
+            .default_value("10"),
+        Arg::new("iterations")
+            .long("iterations")
+            .short('n')
+            .takes_value(true)
+            .requires("bench")
+            .default_value("100"),
+    ]
+}
+
+/// The options [`bench_args`] asked for, or `None` without `--bench`.
+pub fn bench_options(matches: &ArgMatches) -> Option<bench::Options> {
+    if !matches.is_present("bench") {
+        return None;
+    }
+
+    Some(bench::Options {
+        warmup: matches.value_of_t_or_exit("warmup"),
+        iterations: matches.value_of_t_or_exit("iterations"),
+    })
+}
+
+/// `--sandbox` and the limits it holds each day to.
+pub fn sandbox_args() -> Vec<Arg<'static>> {
+    vec![
+        Arg::new("sandbox")
+            .long("sandbox")
+            .help("Run each day in a child process with time and memory limits"),
+        Arg::new("timeout")
+            .long("timeout")
+            .takes_value(true)
+            .requires("sandbox")
+            .default_value("10")
+            .help("Wall-clock seconds allowed per day"),
+        Arg::new("cpu")
+            .long("cpu")
+            .takes_value(true)
+            .requires("sandbox")
+            .help("CPU seconds allowed per day; defaults to the timeout"),
+        Arg::new("memory")
+            .long("memory")
+            .takes_value(true)
+            .requires("sandbox")
+            .default_value("1024")
+            .help("Memory allowed per day, in MiB"),
+    ]
+}
+
+/// The limits [`sandbox_args`] asked for, or `None` without `--sandbox`.
+pub fn limits(matches: &ArgMatches) -> Option<Limits> {
+    if !matches.is_present("sandbox") {
+        return None;
+    }
+
+    let timeout: f64 = matches.value_of_t_or_exit("timeout");
+    let cpu: f64 = match matches.value_of("cpu") {
+        Some(_) => matches.value_of_t_or_exit("cpu"),
+        None => timeout,
+    };
+    let memory: u64 = matches.value_of_t_or_exit("memory");
+    Some(Limits {
+        timeout: Duration::from_secs_f64(timeout),
+        cpu: Duration::from_secs_f64(cpu),
+        memory: memory * 1024 * 1024,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use clap::{App, ArgMatches};
+
+    use super::{bench_args, bench_options, limits, sandbox_args, trace_args};
+
+    fn parse(args: &[&str]) -> ArgMatches {
+        App::new("runner")
+            .args(trace_args(true))
+            .args(bench_args())
+            .args(sandbox_args())
+            .try_get_matches_from(std::iter::once("runner").chain(args.iter().copied()))
+            .unwrap()
+    }
+
+    #[test]
+    fn nothing_is_asked_for_by_default() {
+        let matches = parse(&[]);
+        assert!(limits(&matches).is_none());
+        assert!(bench_options(&matches).is_none());
+    }
+
+    #[test]
+    fn cpu_time_defaults_to_the_timeout() {
+        let limits = limits(&parse(&["--sandbox", "--timeout", "2.5", "--memory", "64"])).unwrap();
+        assert_eq!(limits.timeout, Duration::from_millis(2500));
+        assert_eq!(limits.cpu, limits.timeout);
+        assert_eq!(limits.memory, 64 * 1024 * 1024);
+    }
+
+    #[test]
+    fn verbosity_can_keep_off_short_flags() {
+        let app = App::new("runner").args(trace_args(false));
+        assert!(app
+            .clone()
+            .try_get_matches_from(["runner", "--verbose", "--verbose"])
+            .is_ok());
+        assert!(app.try_get_matches_from(["runner", "-v"]).is_err());
+    }
+}
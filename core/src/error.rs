@@ -0,0 +1,269 @@
+use std::{
+    fmt::{self, Display},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+/// Where in a puzzle input something went wrong: a 1-based line and column,
+/// and how many characters from there are at fault.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+impl Span {
+    /// Locates `part` within `text`, which it must be a slice of; `None`
+    /// when it isn't.
+    pub fn of(text: &str, part: &str) -> Option<Span> {
+        let start = (part.as_ptr() as usize).checked_sub(text.as_ptr() as usize)?;
+        let before = text.get(..start)?;
+        text.get(start..start + part.len())?;
+
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Some(Span {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            len: part.chars().count(),
+        })
+    }
+
+    /// Turns a span found within some slice into one within the text that
+    /// slice sits at `outer` in.
+    fn within(self, outer: Span) -> Span {
+        Span {
+            line: outer.line + self.line - 1,
+            column: match self.line {
+                1 => outer.column + self.column - 1,
+                _ => self.column,
+            },
+            len: self.len,
+        }
+    }
+}
+
+/// What was wrong with an input.
+#[derive(Debug)]
+pub enum Kind {
+    /// Input that isn't in the puzzle's format. Holds what was expected
+    /// instead.
+    Malformed(String),
+    /// A number that wouldn't parse.
+    Number(ParseIntError),
+    /// Input in the right format that the puzzle has no answer for.
+    Unsolvable(String),
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Malformed(expected) => write!(f, "expected {}", expected),
+            Kind::Number(error) => write!(f, "invalid number: {}", error),
+            Kind::Unsolvable(why) => write!(f, "no answer: {}", why),
+        }
+    }
+}
+
+/// An error in a puzzle input, pinned to the day it came from and, where
+/// the solution knows it, the stretch of input at fault.
+#[derive(Debug)]
+pub struct Error {
+    pub kind: Kind,
+    /// The year and day, filled in by [`Entry`](crate::Entry) on the way
+    /// out.
+    pub puzzle: Option<(u16, u8)>,
+    pub span: Option<Span>,
+}
+
+impl Error {
+    pub fn new(kind: Kind) -> Self {
+        Error {
+            kind,
+            puzzle: None,
+            span: None,
+        }
+    }
+
+    pub fn malformed(expected: impl Into<String>) -> Self {
+        Error::new(Kind::Malformed(expected.into()))
+    }
+
+    pub fn unsolvable(why: impl Into<String>) -> Self {
+        Error::new(Kind::Unsolvable(why.into()))
+    }
+
+    /// Points the error at `part`, a slice of `text`.
+    pub fn at(mut self, text: &str, part: &str) -> Self {
+        self.span = Span::of(text, part);
+        self
+    }
+
+    /// Moves a span found within `part` out to where `part` sits in `text`.
+    /// An error without a span is pinned to the whole of `part`.
+    pub fn within(mut self, text: &str, part: &str) -> Self {
+        self.span = match (self.span, Span::of(text, part)) {
+            (Some(inner), Some(outer)) => Some(inner.within(outer)),
+            (None, outer) => outer,
+            (inner, None) => inner,
+        };
+        self
+    }
+
+    /// Shows the error as a diagnostic: the message, where it happened and,
+    /// with a span, the offending line of `input` with a caret under it.
+    pub fn render(&self, input: &str) -> String {
+        let mut output = format!("error: {}\n", self.kind);
+        let span = match self.span {
+            Some(span) => span,
+            None => {
+                if let Some((year, day)) = self.puzzle {
+                    output.push_str(&format!(" --> {} day {}\n", year, day));
+                }
+                return output;
+            }
+        };
+
+        let gutter = " ".repeat(span.line.to_string().len());
+        let location = match self.puzzle {
+            Some((year, day)) => format!("{} day {}, ", year, day),
+            None => String::new(),
+        };
+        let line = input.lines().nth(span.line - 1).unwrap_or_default();
+
+        output.push_str(&format!(
+            "{}--> {}line {}, column {}\n",
+            gutter, location, span.line, span.column
+        ));
+        output.push_str(&format!("{} |\n", gutter));
+        output.push_str(&format!("{} | {}\n", span.line, line));
+        output.push_str(&format!(
+            "{} | {}{}\n",
+            gutter,
+            " ".repeat(span.column - 1),
+            "^".repeat(span.len.max(1))
+        ));
+        output
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((year, day)) = self.puzzle {
+            write!(f, "{} day {}, ", year, day)?;
+        }
+        if let Some(span) = self.span {
+            write!(f, "line {}, column {}: ", span.line, span.column)?;
+        }
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(error: ParseIntError) -> Self {
+        Error::new(Kind::Number(error))
+    }
+}
+
+/// Parses `part`, a slice of `text`, as a number, pointing at it if it
+/// isn't one.
+pub fn number<T: FromStr<Err = ParseIntError>>(text: &str, part: &str) -> Result<T, Error> {
+    part.parse()
+        .map_err(|error| Error::from(error).at(text, part))
+}
+
+/// Pins a typed error that escaped a solution to its year and day. Other
+/// errors pass through untouched.
+pub(crate) fn locate(
+    error: Box<dyn std::error::Error>,
+    year: u16,
+    day: u8,
+) -> Box<dyn std::error::Error> {
+    match error.downcast::<Error>() {
+        Ok(mut error) => {
+            error.puzzle.get_or_insert((year, day));
+            error
+        }
+        Err(error) => error,
+    }
+}
+
+/// The rendered diagnostic for `error`, when it's a typed error that knows
+/// where in `input` it happened.
+pub fn diagnose(error: &(dyn std::error::Error + 'static), input: &str) -> Option<String> {
+    error
+        .downcast_ref::<Error>()
+        .filter(|error| error.span.is_some())
+        .map(|error| error.render(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{number, Error, Span};
+
+    const INPUT: &str = "1-3 a: abcde\n1-3b: cdefg\n2-9 c: ccccccccc";
+
+    #[test]
+    fn spans_count_lines_and_columns() {
+        let line = INPUT.lines().nth(1).unwrap();
+        assert_eq!(
+            Span::of(INPUT, &line[2..4]),
+            Some(Span {
+                line: 2,
+                column: 3,
+                len: 2
+            })
+        );
+        assert_eq!(Span::of(INPUT, "elsewhere"), None);
+    }
+
+    #[test]
+    fn spans_move_out_to_the_enclosing_text() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let policy = &line[..4];
+        let error = Error::malformed("a policy like `1-3 a`")
+            .at(policy, &policy[2..])
+            .within(INPUT, policy);
+
+        assert_eq!(
+            error.span,
+            Some(Span {
+                line: 2,
+                column: 3,
+                len: 2
+            })
+        );
+    }
+
+    #[test]
+    fn renders_a_caret_under_the_offending_input() {
+        let line = INPUT.lines().nth(2).unwrap();
+        let mut error = number::<u8>(INPUT, &line[4..5]).unwrap_err();
+        error.puzzle = Some((2020, 2));
+
+        assert_eq!(
+            error.to_string(),
+            "2020 day 2, line 3, column 5: invalid number: invalid digit found in string"
+        );
+        assert_eq!(
+            error.render(INPUT),
+            concat!(
+                "error: invalid number: invalid digit found in string\n",
+                " --> 2020 day 2, line 3, column 5\n",
+                "  |\n",
+                "3 | 2-9 c: ccccccccc\n",
+                "  |     ^\n",
+            )
+        );
+    }
+
+    #[test]
+    fn empty_spans_still_get_a_caret() {
+        let input = "1\n\n3";
+        let error = number::<u32>(input, &input[2..2]).unwrap_err();
+
+        assert!(error.render(input).ends_with("2 | \n  | ^\n"));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{parse_with, Input, Part, Solution};
+
+/// A worked example from a puzzle description, with the answer the
+/// description gives for each part. Parts the example doesn't cover are
+/// left as `None`.
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+/// Parses and solves every example, panicking on the first answer that
+/// doesn't match.
+pub fn assert_examples<S: Solution + 'static>(examples: &[Example]) {
+    for (index, example) in examples.iter().enumerate() {
+        let parsed = parse_with::<S>(&Input::new(example.input))
+            .unwrap_or_else(|error| panic!("example {} failed to parse: {}", index + 1, error));
+
+        for (part, expected) in [(Part::One, example.part1), (Part::Two, example.part2)] {
+            if let Some(expected) = expected {
+                let answer = parsed.solve(&part).unwrap_or_else(|error| {
+                    panic!("example {} part {} failed: {}", index + 1, part, error)
+                });
+                assert_eq!(
+                    answer.to_string(),
+                    expected,
+                    "example {} part {}",
+                    index + 1,
+                    part
+                );
+            }
+        }
+    }
+}
+
+/// A block in a puzzle description that looks like it could be an example
+/// input: a fenced code block or a run of `- ` list items.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Candidate {
+    /// 1-based line in the description where the block starts.
+    pub line: usize,
+    pub text: String,
+}
+
+impl Candidate {
+    /// Renders the block as an `Example` literal ready to paste into a
+    /// day's tests, answers left for filling in by hand.
+    pub fn to_fixture(&self) -> String {
+        format!(
+            "Example {{\n    input: \"\\\n{}\",\n    part1: None,\n    part2: None,\n}},",
+            self.text.replace('\\', "\\\\").replace('"', "\\\"")
+        )
+    }
+}
+
+/// Pulls candidate example blocks out of a day's markdown puzzle
+/// description.
+pub fn extract(description: &str) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    let mut block: Option<(usize, Vec<&str>, bool)> = None;
+
+    for (index, line) in description.lines().enumerate() {
+        block = match (block.take(), line) {
+            (None, line) if line.starts_with("```") => Some((index + 2, Vec::new(), true)),
+            (None, line) if line.starts_with("- ") => Some((index + 1, vec![&line[2..]], false)),
+            (None, _) => None,
+            (Some((start, lines, true)), line) if line.starts_with("```") => {
+                candidates.push(candidate(start, &lines));
+                None
+            }
+            (Some((start, mut lines, true)), line) => {
+                lines.push(line);
+                Some((start, lines, true))
+            }
+            (Some((start, mut lines, false)), line) if line.starts_with("- ") => {
+                lines.push(&line[2..]);
+                Some((start, lines, false))
+            }
+            (Some((start, lines, false)), _) => {
+                candidates.push(candidate(start, &lines));
+                None
+            }
+        };
+    }
+
+    if let Some((start, lines, false)) = block {
+        candidates.push(candidate(start, &lines));
+    }
+    candidates
+}
+
+fn candidate(line: usize, lines: &[&str]) -> Candidate {
+    Candidate {
+        line,
+        text: lines.join("\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract, Candidate};
+
+    const DESCRIPTION: &str = "# Part One
+
+For example:
+
+- 1721
+- 979
+
+A map:
+
+```text
+..#
+#..
+```
+
+- 1
+- 2";
+
+    #[test]
+    fn finds_lists_and_fenced_blocks() {
+        assert_eq!(
+            extract(DESCRIPTION),
+            vec![
+                Candidate {
+                    line: 5,
+                    text: "1721\n979".to_string()
+                },
+                Candidate {
+                    line: 11,
+                    text: "..#\n#..".to_string()
+                },
+                Candidate {
+                    line: 15,
+                    text: "1\n2".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fixtures_escape_quotes() {
+        let candidate = Candidate {
+            line: 1,
+            text: "say \"hi\"".to_string(),
+        };
+        assert_eq!(
+            candidate.to_fixture(),
+            "Example {\n    input: \"\\\nsay \\\"hi\\\"\",\n    part1: None,\n    part2: None,\n},"
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bench::{Measurement, Stage},
+    state, Result,
+};
+
+const FILE_NAME: &str = "bench-history.jsonl";
+
+/// One benchmark measurement, tagged with where and against what it ran.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Run {
+    pub timestamp: u64,
+    pub commit: String,
+    pub input_hash: String,
+    #[serde(flatten)]
+    pub measurement: Measurement,
+}
+
+/// Appends `measurements` for a run against `input` to the history file.
+pub fn record(measurements: &[Measurement], input: &str) -> Result<()> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let commit = current_commit();
+    let input_hash = hash(input);
+
+    let path = state::path(FILE_NAME);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for measurement in measurements {
+        let run = Run {
+            timestamp,
+            commit: commit.clone(),
+            input_hash: input_hash.clone(),
+            measurement: measurement.clone(),
+        };
+        writeln!(file, "{}", serde_json::to_string(&run)?)?;
+    }
+
+    Ok(())
+}
+
+/// Every recorded run, oldest first.
+pub fn load() -> Result<Vec<Run>> {
+    let path = state::path(FILE_NAME);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// The short hash of the workspace's `HEAD`, suffixed with `-dirty` when the
+/// tree has uncommitted changes, or `unknown` when it isn't a git checkout.
+/// Where the binary happens to be run from doesn't come into it.
+pub fn current_commit() -> String {
+    let workspace = state::workspace();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(&workspace)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(status) if !status.is_empty() => format!("{}-dirty", commit),
+            _ => commit,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// FNV-1a, so the hash of an input stays the same across Rust releases.
+pub fn hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+#[derive(Debug)]
+pub struct Comparison {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Run,
+    pub current: Run,
+    /// Relative change in median time, as a percentage of the baseline.
+    pub change: f64,
+    pub verdict: Verdict,
+}
+
+/// Compares the latest run of each year/day/stage/input against a baseline
+/// run of the same key. The baseline is the latest run from a commit
+/// starting with `baseline`, or, when that's `None`, the latest run from any
+/// commit other than the current one. Changes beyond `threshold` percent
+/// either way are flagged.
+pub fn compare(runs: &[Run], baseline: Option<&str>, threshold: f64) -> Vec<Comparison> {
+    let mut by_key: HashMap<(u16, u8, String, String), Vec<&Run>> = HashMap::new();
+    for run in runs {
+        let key = (
+            run.measurement.year,
+            run.measurement.day,
+            run.measurement.stage.to_string(),
+            run.input_hash.clone(),
+        );
+        by_key.entry(key).or_default().push(run);
+    }
+
+    let mut comparisons: Vec<Comparison> = by_key
+        .into_values()
+        .filter_map(|runs| {
+            let current = *runs.iter().max_by_key(|run| run.timestamp)?;
+            let baseline = runs
+                .iter()
+                .filter(|run| match baseline {
+                    Some(commit) => run.commit.starts_with(commit),
+                    None => run.commit != current.commit,
+                })
+                .filter(|run| !std::ptr::eq(**run, current))
+                .max_by_key(|run| run.timestamp)?;
+
+            let before = baseline.measurement.stats.median.as_secs_f64();
+            let after = current.measurement.stats.median.as_secs_f64();
+            let change = if before > 0.0 {
+                (after - before) / before * 100.0
+            } else {
+                0.0
+            };
+            let verdict = if change > threshold {
+                Verdict::Regressed
+            } else if change < -threshold {
+                Verdict::Improved
+            } else {
+                Verdict::Unchanged
+            };
+
+            Some(Comparison {
+                year: current.measurement.year,
+                day: current.measurement.day,
+                stage: current.measurement.stage,
+                baseline: (*baseline).clone(),
+                current: current.clone(),
+                change,
+                verdict,
+            })
+        })
+        .collect();
+
+    comparisons.sort_by_key(|comparison| {
+        (
+            comparison.year,
+            comparison.day,
+            comparison.stage.to_string(),
+        )
+    });
+    comparisons
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, hash, Run, Verdict};
+    use crate::{
+        bench::{Measurement, Stage, Stats},
+        Part,
+    };
+
+    fn run(timestamp: u64, commit: &str, median_us: u64) -> Run {
+        let median = Duration::from_micros(median_us);
+        Run {
+            timestamp,
+            commit: commit.to_string(),
+            input_hash: "abc".to_string(),
+            measurement: Measurement {
+                year: 2022,
+                day: 3,
+                stage: Stage::Solve(Part::One),
+                stats: Stats {
+                    samples: 10,
+                    min: median,
+                    median,
+                    mean: median,
+                    stddev: Duration::ZERO,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn flags_regressions_past_the_threshold() {
+        let runs = vec![run(1, "aaaa", 100), run(2, "bbbb", 130)];
+        let comparisons = compare(&runs, None, 10.0);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].verdict, Verdict::Regressed);
+        assert!((comparisons[0].change - 30.0).abs() < 1e-9);
+
+        let comparisons = compare(&runs, None, 50.0);
+        assert_eq!(comparisons[0].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn uses_the_chosen_baseline() {
+        let runs = vec![
+            run(1, "aaaa", 200),
+            run(2, "bbbb", 100),
+            run(3, "cccc", 105),
+        ];
+        assert_eq!(compare(&runs, None, 10.0)[0].verdict, Verdict::Unchanged);
+        assert_eq!(
+            compare(&runs, Some("aa"), 10.0)[0].verdict,
+            Verdict::Improved
+        );
+        assert!(compare(&runs, Some("zz"), 10.0).is_empty());
+    }
+
+    #[test]
+    fn history_lines_round_trip() {
+        let line = serde_json::to_string(&run(1, "aaaa", 100)).unwrap();
+        let parsed: Run = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed.measurement.stats.median, Duration::from_micros(100));
+        assert_eq!(parsed.measurement.stage, Stage::Solve(Part::One));
+    }
+
+    #[test]
+    fn stable_input_hash() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    fs,
+    io::{self, Read},
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+use crate::Result;
+
+/// A puzzle input with the quirks of however it was saved ironed out: no
+/// byte-order mark, `\n` line endings and no blank lines at the end. Every
+/// day parses one of these rather than the raw file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input(String);
+
+impl Input {
+    pub fn new(raw: &str) -> Self {
+        let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+        let lines: Vec<&str> = raw.lines().collect();
+        let kept = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |last| last + 1);
+        Input(lines[..kept].join("\n"))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// `input` as assorted editors and downloads might save it, each labelled
+/// with what's different about it. Every one should parse the same as
+/// `input` itself.
+pub fn variants(input: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("CRLF line endings", input.replace('\n', "\r\n")),
+        ("a byte-order mark", format!("\u{feff}{}", input)),
+        ("a trailing newline", format!("{}\n", input)),
+        ("trailing blank lines", format!("{}\n\n  \n", input)),
+    ]
+}
+
+/// The conventional location of a day's puzzle input, `<dir>/<day>.txt`.
+pub fn default_path(input_dir: &str, day: u8) -> PathBuf {
+    Path::new(input_dir).join(format!("{}.txt", day))
+}
+
+/// Reads a puzzle input from `path`, or from stdin when `path` is `-`.
+pub fn read(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        return Ok(buffer);
+    }
+
+    fs::read_to_string(path)
+        .map_err(|error| format!("Couldn't read input `{}`: {}", path.display(), error).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{variants, Input};
+
+    #[test]
+    fn normalizes_every_variant() {
+        let input = "1-3 a: abcde\n\n  indented\n2-9 c: ccccccccc";
+        for (variant, text) in variants(input) {
+            assert_eq!(Input::new(&text).as_str(), input, "{}", variant);
+        }
+    }
+
+    #[test]
+    fn keeps_blank_lines_between_blocks() {
+        assert_eq!(Input::new("1\r\n\r\n2\r\n\r\n").as_str(), "1\n\n2");
+        assert_eq!(Input::new("\n\n").as_str(), "");
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{error::Error, fmt::Display};
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod examples;
+pub mod history;
+pub mod input;
+pub mod report;
+pub mod runner;
+pub mod sandbox;
+pub mod select;
+pub mod state;
+pub mod submissions;
+pub mod trace;
+pub mod visual;
+
+pub use answer::Answer;
+pub use input::Input;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u8(match self {
+            Part::One => 1,
+            Part::Two => 2,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            part => Err(de::Error::custom(format!("no part {}", part))),
+        }
+    }
+}
+
+/// A single day's puzzle. The input is parsed once into `Parsed`, and both
+/// parts are solved against that same model. Solutions show their working
+/// through [`trace`], not their answers.
+pub trait Solution {
+    type Parsed;
+    type Answer: Into<Answer>;
+
+    fn parse(input: &Input) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Answer>;
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Answer>;
+}
+
+/// A parsed puzzle input, ready to have either part solved against it.
+pub trait Parsed {
+    fn solve(&self, part: &Part) -> Result<Answer>;
+}
+
+struct Model<S: Solution>(S::Parsed);
+
+impl<S: Solution> Parsed for Model<S> {
+    fn solve(&self, part: &Part) -> Result<Answer> {
+        match part {
+            Part::One => S::part1(&self.0).map(Into::into),
+            Part::Two => S::part2(&self.0).map(Into::into),
+        }
+    }
+}
+
+fn parse_with<S: Solution + 'static>(input: &Input) -> Result<Box<dyn Parsed>> {
+    Ok(Box::new(Model::<S>(S::parse(input)?)))
+}
+
+/// A registered solution for one day of one year.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    parse: fn(&Input) -> Result<Box<dyn Parsed>>,
+}
+
+impl Entry {
+    pub fn new<S: Solution + 'static>(year: u16, day: u8) -> Self {
+        Entry {
+            year,
+            day,
+            parse: parse_with::<S>,
+        }
+    }
+
+    /// Parses `input`. Typed errors from parsing or from solving what it
+    /// parsed come out pinned to this entry's year and day.
+    pub fn parse(&self, input: &Input) -> Result<Box<dyn Parsed>> {
+        let (year, day) = (self.year, self.day);
+        match (self.parse)(input) {
+            Ok(parsed) => Ok(Box::new(Located { year, day, parsed })),
+            Err(error) => Err(error::locate(error, year, day)),
+        }
+    }
+}
+
+/// A parsed input that pins its solve errors to its day.
+struct Located {
+    year: u16,
+    day: u8,
+    parsed: Box<dyn Parsed>,
+}
+
+impl Parsed for Located {
+    fn solve(&self, part: &Part) -> Result<Answer> {
+        self.parsed
+            .solve(part)
+            .map_err(|error| error::locate(error, self.year, self.day))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Entry, Input, Part, Result, Solution};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<u32>;
+        type Answer = u32;
+
+        fn parse(input: &Input) -> Result<Vec<u32>> {
+            Ok(input
+                .lines()
+                .map(|line| line.parse())
+                .collect::<std::result::Result<_, _>>()?)
+        }
+
+        fn part1(numbers: &Vec<u32>) -> Result<u32> {
+            Ok(numbers.iter().sum())
+        }
+
+        fn part2(numbers: &Vec<u32>) -> Result<u32> {
+            Ok(numbers.iter().product())
+        }
+    }
+
+    #[test]
+    fn both_parts_share_one_parse() {
+        let parsed = Entry::new::<Sum>(0, 0)
+            .parse(&Input::new("2\n3\n4"))
+            .unwrap();
+        assert_eq!(parsed.solve(&Part::One).unwrap().to_string(), "9");
+        assert_eq!(parsed.solve(&Part::Two).unwrap().to_string(), "24");
+    }
+
+    #[test]
+    fn parse_errors_surface_before_solving() {
+        assert!(Entry::new::<Sum>(0, 0).parse(&Input::new("2\nx")).is_err());
+    }
+}
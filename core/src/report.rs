@@ -0,0 +1,136 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::{runner::Record, visual::Screen};
+
+/// How a runner prints its records. `Human` is left to each runner, since
+/// each has its own long-standing layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Json,
+    Tsv,
+}
+
+pub const FORMATS: [&str; 3] = ["human", "json", "tsv"];
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!("Format `{}` not recognized", s)),
+        }
+    }
+}
+
+impl Format {
+    /// Where anything that isn't a record goes, such as animated frames:
+    /// stdout for people, stderr when stdout is for another program.
+    pub fn screen(&self) -> Screen {
+        match self {
+            Format::Human => Screen::Stdout,
+            _ => Screen::Stderr,
+        }
+    }
+
+    pub fn header<R: Row>(&self) -> Option<&'static str> {
+        match self {
+            Format::Tsv => Some(R::TSV_HEADER),
+            _ => None,
+        }
+    }
+
+    /// Renders a row as one line of JSON or TSV, or `None` for `Human`.
+    pub fn line<R: Row>(&self, row: &R) -> Option<String> {
+        match self {
+            Format::Human => None,
+            Format::Json => Some(serde_json::to_string(row).expect("rows always serialize")),
+            Format::Tsv => Some(
+                row.tsv_fields()
+                    .iter()
+                    .map(|field| field.replace(['\t', '\n', '\r'], " "))
+                    .collect::<Vec<_>>()
+                    .join("\t"),
+            ),
+        }
+    }
+}
+
+/// Anything a runner prints one line per item of in the machine formats.
+pub trait Row: Serialize {
+    const TSV_HEADER: &'static str;
+
+    fn tsv_fields(&self) -> Vec<String>;
+}
+
+impl Row for Record {
+    const TSV_HEADER: &'static str = "year\tday\tpart\tstatus\tanswer\telapsed_us\terror";
+
+    fn tsv_fields(&self) -> Vec<String> {
+        vec![
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.status.to_string(),
+            self.answer.clone().unwrap_or_default(),
+            self.elapsed.as_micros().to_string(),
+            self.error.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Format;
+    use crate::{
+        runner::{Record, Status},
+        Part,
+    };
+
+    fn record() -> Record {
+        Record {
+            year: 2022,
+            day: 5,
+            part: Part::Two,
+            status: Status::Ok,
+            answer: Some("BRQWDBBJM".to_string()),
+            elapsed: Duration::from_micros(311),
+            error: None,
+            events: Vec::new(),
+            frames: Vec::new(),
+            warning: None,
+            diagnostic: None,
+        }
+    }
+
+    #[test]
+    fn json_lines() {
+        assert_eq!(
+            Format::Json.line(&record()).unwrap(),
+            r#"{"year":2022,"day":5,"part":2,"status":"ok","answer":"BRQWDBBJM","elapsed_us":311,"error":null}"#
+        );
+    }
+
+    #[test]
+    fn tsv_lines() {
+        let mut record = record();
+        record.status = Status::Error;
+        record.error = Some("bad\tline\n".to_string());
+        assert_eq!(
+            Format::Tsv.line(&record).unwrap(),
+            "2022\t5\t2\terror\tBRQWDBBJM\t311\tbad line "
+        );
+        assert_eq!(Format::Human.line(&record), None);
+        assert_eq!(
+            Format::Tsv.header::<Record>(),
+            Some("year\tday\tpart\tstatus\tanswer\telapsed_us\terror")
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+    time::Duration,
+    time::Instant,
+};
+
+use serde::{Serialize, Serializer};
+
+use crate::{
+    error, submissions,
+    trace::{self, Event},
+    visual::{self, Frame},
+    Entry, Input, Part,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+    /// Killed by the sandbox for running past its time or CPU limit.
+    Timeout,
+    /// Killed by the sandbox for allocating past its memory limit.
+    Oom,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Status::Ok => "ok",
+            Status::Error => "error",
+            Status::Timeout => "timeout",
+            Status::Oom => "oom",
+        })
+    }
+}
+
+/// The result of running one part of one day.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub status: Status,
+    pub answer: Option<String>,
+    #[serde(rename = "elapsed_us", serialize_with = "as_micros")]
+    pub elapsed: Duration,
+    pub error: Option<String>,
+    /// What the solution said about its working, at whatever
+    /// [`trace`] level was set. Events from parsing go with the first part.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<Event>,
+    /// What the solution drew, when [`visual`] was enabled. Frames from
+    /// parsing go with the first part.
+    #[serde(skip)]
+    pub frames: Vec<Frame>,
+    /// Set when the answer is one the site already turned down.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    /// The error rendered against the input, when it knows where in the
+    /// input it happened.
+    #[serde(skip)]
+    pub diagnostic: Option<String>,
+}
+
+impl Record {
+    pub fn failed(entry: &Entry, part: Part, error: &dyn Display) -> Self {
+        Record {
+            year: entry.year,
+            day: entry.day,
+            part,
+            status: Status::Error,
+            answer: None,
+            elapsed: Duration::ZERO,
+            error: Some(error.to_string()),
+            events: Vec::new(),
+            frames: Vec::new(),
+            warning: None,
+            diagnostic: None,
+        }
+    }
+}
+
+fn as_micros<S: Serializer>(elapsed: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(elapsed.as_micros())
+}
+
+/// Parses `input` once and solves each of `parts` against it, timing only
+/// the solve. A panic in the solution fails only the parts it affects.
+pub fn run(entry: &Entry, input: &str, parts: &[Part]) -> Vec<Record> {
+    let mut records = Vec::new();
+    run_each(entry, input, parts, |record| records.push(record));
+    records
+}
+
+/// Like [`run`], but hands each part's record over as soon as it's solved.
+pub fn run_each(entry: &Entry, input: &str, parts: &[Part], mut each: impl FnMut(Record)) {
+    let input = Input::new(input);
+    let (parsed, mut events, mut frames) = observe(|| entry.parse(&input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            let diagnostic = error::diagnose(&*error, &input);
+            return parts
+                .iter()
+                .map(|&part| Record {
+                    diagnostic: diagnostic.clone(),
+                    events: std::mem::take(&mut events),
+                    frames: std::mem::take(&mut frames),
+                    ..Record::failed(entry, part, &error)
+                })
+                .for_each(each);
+        }
+    };
+
+    let submissions = submissions::load().unwrap_or_else(|error| {
+        eprintln!(
+            "Couldn't load past submissions, so known wrong answers won't be flagged: {}",
+            error
+        );
+        Default::default()
+    });
+    for &part in parts {
+        let start = Instant::now();
+        let (result, solve_events, solve_frames) = observe(|| parsed.solve(&part));
+        let elapsed = start.elapsed();
+        let mut events = std::mem::take(&mut events);
+        events.extend(solve_events);
+        let mut frames = std::mem::take(&mut frames);
+        frames.extend(solve_frames);
+        let mut record = match result {
+            Ok(answer) => Record {
+                year: entry.year,
+                day: entry.day,
+                part,
+                status: Status::Ok,
+                answer: Some(answer.to_string()),
+                elapsed,
+                error: None,
+                events,
+                frames,
+                warning: None,
+                diagnostic: None,
+            },
+            Err(error) => Record {
+                elapsed,
+                events,
+                frames,
+                diagnostic: error::diagnose(&*error, &input),
+                ..Record::failed(entry, part, &error)
+            },
+        };
+
+        // Marks answers the submission log says are wrong.
+        if let Some(answer) = &record.answer {
+            record.warning = submissions::flag(&submissions, entry.year, entry.day, part, answer);
+        }
+        each(record);
+    }
+}
+
+/// Calls out, on stderr, every answer already known to be wrong and every
+/// error that can point at its input. A parse error shared by both parts is
+/// only shown once.
+pub fn warn(records: &[Record]) {
+    let mut diagnostics: Vec<&str> = Vec::new();
+    for record in records {
+        if let Some(diagnostic) = &record.diagnostic {
+            if !diagnostics.contains(&diagnostic.as_str()) {
+                eprint!("{}", diagnostic);
+                diagnostics.push(diagnostic);
+            }
+        }
+        if let Some(warning) = &record.warning {
+            eprintln!(
+                "!!! {} day {} part {}: KNOWN WRONG: {}",
+                record.year, record.day, record.part, warning
+            );
+        }
+    }
+}
+
+/// Runs `f` under [`guard`], collecting the events and frames it emits.
+fn observe<T>(f: impl FnOnce() -> crate::Result<T>) -> (crate::Result<T>, Vec<Event>, Vec<Frame>) {
+    let ((result, events), frames) = visual::capture(|| trace::capture(|| guard(f)));
+    (result, events, frames)
+}
+
+thread_local! {
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, turning a panic into an error carrying its message and where
+/// it happened.
+pub(crate) fn guard<T>(f: impl FnOnce() -> crate::Result<T>) -> crate::Result<T> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !GUARDED.with(Cell::get) {
+                return default(info);
+            }
+
+            let message = match info.payload().downcast_ref::<&str>() {
+                Some(message) => message.to_string(),
+                None => match info.payload().downcast_ref::<String>() {
+                    Some(message) => message.clone(),
+                    None => "Box<dyn Any>".to_string(),
+                },
+            };
+            let panic = match info.location() {
+                Some(location) => format!("panicked at {}: {}", location, message),
+                None => format!("panicked: {}", message),
+            };
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(panic));
+        }));
+    });
+
+    GUARDED.with(|guarded| guarded.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    GUARDED.with(|guarded| guarded.set(false));
+
+    result.unwrap_or_else(|_| {
+        let panic = LAST_PANIC.with(|last| last.borrow_mut().take());
+        Err(From::from(panic.unwrap_or_else(|| "panicked".to_string())))
+    })
+}
+
+/// One failed record per part, for when a day can't even be parsed.
+pub fn failed(entry: &Entry, parts: &[Part], error: &dyn Display) -> Vec<Record> {
+    parts
+        .iter()
+        .map(|&part| Record::failed(entry, part, error))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, Status};
+    use crate::{error, Entry, Input, Part, Result, Solution};
+
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Parsed = u32;
+        type Answer = u32;
+
+        fn parse(input: &Input) -> Result<u32> {
+            Ok(input.parse().expect("not a number"))
+        }
+
+        fn part1(number: &u32) -> Result<u32> {
+            Ok(number + 1)
+        }
+
+        fn part2(_: &u32) -> Result<u32> {
+            panic!("part two is broken")
+        }
+    }
+
+    #[test]
+    fn panics_fail_only_their_part() {
+        let records = run(&Entry::new::<Fragile>(0, 0), "1", &[Part::One, Part::Two]);
+
+        assert_eq!(records[0].answer.as_deref(), Some("2"));
+        assert_eq!(records[1].status, Status::Error);
+        let error = records[1].error.as_deref().unwrap();
+        assert!(error.starts_with("panicked at core/src/runner.rs:"));
+        assert!(error.ends_with(": part two is broken"));
+    }
+
+    #[test]
+    fn panics_while_parsing_fail_every_part() {
+        let records = run(&Entry::new::<Fragile>(0, 0), "x", &[Part::One, Part::Two]);
+
+        assert!(records.iter().all(|record| record.status == Status::Error));
+        assert!(records[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .ends_with(": not a number: ParseIntError { kind: InvalidDigit }"));
+    }
+
+    struct Strict;
+
+    impl Solution for Strict {
+        type Parsed = u32;
+        type Answer = u32;
+
+        fn parse(input: &Input) -> Result<u32> {
+            Ok(error::number(input, input)?)
+        }
+
+        fn part1(number: &u32) -> Result<u32> {
+            Ok(*number)
+        }
+
+        fn part2(number: &u32) -> Result<u32> {
+            Ok(*number)
+        }
+    }
+
+    #[test]
+    fn input_errors_point_at_the_input() {
+        let records = run(&Entry::new::<Strict>(2020, 1), "12x\n", &[Part::One]);
+
+        assert_eq!(
+            records[0].error.as_deref(),
+            Some("2020 day 1, line 1, column 1: invalid number: invalid digit found in string")
+        );
+        assert!(records[0]
+            .diagnostic
+            .as_deref()
+            .unwrap()
+            .ends_with("1 | 12x\n  | ^^^\n"));
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    env,
+    io::{self, BufRead, BufReader, Read, Write},
+    process::{exit, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    runner::{self, Record, Status},
+    trace::{self, Event, Level},
+    visual::{self, Frame},
+    Entry, Part,
+};
+
+/// Set on a sandboxed child to the `year/day/parts/level/frames` it should
+/// solve, so it reports back instead of reading its own command line.
+/// `level` is the trace level's number, 0 for none, and `frames` is 1 when
+/// it should draw.
+const CHILD_ENV: &str = "AOC_SANDBOX_CHILD";
+
+/// How far a sandboxed solution may go before it's killed.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// Wall-clock time for the whole day, parse included.
+    pub timeout: Duration,
+    /// CPU time, enforced with `RLIMIT_CPU`.
+    pub cpu: Duration,
+    /// Address space in bytes, enforced with `RLIMIT_AS`.
+    pub memory: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            timeout: Duration::from_secs(10),
+            cpu: Duration::from_secs(10),
+            memory: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+/// A record as it crosses from the child to the parent.
+#[derive(Serialize, Deserialize)]
+struct Wire {
+    part: Part,
+    ok: bool,
+    answer: Option<String>,
+    error: Option<String>,
+    events: Vec<Event>,
+    frames: Vec<Frame>,
+    warning: Option<String>,
+    diagnostic: Option<String>,
+    elapsed_ns: u64,
+}
+
+/// Call first thing in `main`. In a sandboxed child this solves the day it
+/// was asked for from the input on stdin, writes each record to stdout and
+/// exits; anywhere else it does nothing.
+pub fn serve(entries: &[Entry]) {
+    let spec = match env::var(CHILD_ENV) {
+        Ok(spec) => spec,
+        Err(_) => return,
+    };
+
+    let mut fields = spec.split('/');
+    let year: Option<u16> = fields.next().and_then(|year| year.parse().ok());
+    let day: Option<u8> = fields.next().and_then(|day| day.parse().ok());
+    let parts: Vec<Part> = fields
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter_map(|part| match part {
+            '1' => Some(Part::One),
+            '2' => Some(Part::Two),
+            _ => None,
+        })
+        .collect();
+    trace::set_level(
+        fields
+            .next()
+            .and_then(|level| level.parse().ok())
+            .and_then(Level::from_verbosity),
+    );
+    visual::set_enabled(fields.next() == Some("1"));
+    let entry = entries
+        .iter()
+        .find(|entry| Some(entry.year) == year && Some(entry.day) == day);
+
+    let (entry, mut input) = match entry {
+        Some(entry) => (entry, String::new()),
+        None => {
+            eprintln!("Sandbox asked to run unknown day `{}`", spec);
+            exit(2);
+        }
+    };
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("Sandbox couldn't read its input: {}", error);
+        exit(2);
+    }
+
+    runner::run_each(entry, &input, &parts, |record| {
+        let wire = Wire {
+            part: record.part,
+            ok: record.status == Status::Ok,
+            answer: record.answer,
+            error: record.error,
+            events: record.events,
+            frames: record.frames,
+            warning: record.warning,
+            diagnostic: record.diagnostic,
+            elapsed_ns: record.elapsed.as_nanos() as u64,
+        };
+        let mut stdout = io::stdout().lock();
+        let _ = writeln!(
+            stdout,
+            "{}",
+            serde_json::to_string(&wire).unwrap_or_default()
+        );
+        let _ = stdout.flush();
+    });
+    exit(0)
+}
+
+/// Like [`runner::run`], but in a child process held to `limits`. Parts the
+/// child didn't finish come back as `Timeout`, `Oom` or `Error` records.
+///
+/// Every binary that calls this must call [`serve`] at startup.
+pub fn run(entry: &Entry, input: &str, parts: &[Part], limits: &Limits) -> Vec<Record> {
+    let exe = match env::current_exe() {
+        Ok(exe) => exe,
+        Err(error) => return runner::failed(entry, parts, &error),
+    };
+
+    let spec: String = parts.iter().map(|part| part.to_string()).collect();
+    let mut command = Command::new(exe);
+    let level = trace::level().map_or(0, |level| level as u8);
+    let frames = u8::from(visual::enabled());
+    command.env(
+        CHILD_ENV,
+        format!("{}/{}/{}/{}/{}", entry.year, entry.day, spec, level, frames),
+    );
+
+    let supervised = match supervise(command, input, limits) {
+        Ok(supervised) => supervised,
+        Err(error) => return runner::failed(entry, parts, &error),
+    };
+
+    let mut records: Vec<Record> = supervised
+        .lines
+        .iter()
+        .filter_map(|line| serde_json::from_str::<Wire>(line).ok())
+        .map(|wire| Record {
+            year: entry.year,
+            day: entry.day,
+            part: wire.part,
+            status: if wire.ok { Status::Ok } else { Status::Error },
+            answer: wire.answer,
+            elapsed: Duration::from_nanos(wire.elapsed_ns),
+            error: wire.error,
+            events: wire.events,
+            frames: wire.frames,
+            warning: wire.warning,
+            diagnostic: wire.diagnostic,
+        })
+        .collect();
+
+    let (status, error) = verdict(&supervised, limits);
+    for &part in parts {
+        if records.iter().all(|record| record.part != part) {
+            records.push(Record {
+                status,
+                ..Record::failed(entry, part, &error)
+            });
+        }
+    }
+    records.sort_by_key(|record| parts.iter().position(|&part| part == record.part));
+    records
+}
+
+/// What became of a child process.
+struct Supervised {
+    lines: Vec<String>,
+    stderr: String,
+    status: Option<ExitStatus>,
+    timed_out: bool,
+}
+
+/// Runs `command` with `input` on stdin under `limits`, killing it if it
+/// outlives the timeout.
+fn supervise(mut command: Command, input: &str, limits: &Limits) -> io::Result<Supervised> {
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    rlimit(&mut command, limits);
+
+    let mut child = command.spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = input.to_string();
+    thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let lines = thread::spawn(move || {
+        BufReader::new(stdout)
+            .lines()
+            .map_while(|line| line.ok())
+            .collect()
+    });
+
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let errors = thread::spawn(move || {
+        let mut errors = String::new();
+        let _ = stderr.read_to_string(&mut errors);
+        errors
+    });
+
+    let start = Instant::now();
+    let (status, timed_out) = loop {
+        if let Some(status) = child.try_wait()? {
+            break (Some(status), false);
+        }
+        if start.elapsed() >= limits.timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            break (None, true);
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+
+    Ok(Supervised {
+        lines: lines.join().unwrap_or_default(),
+        stderr: errors.join().unwrap_or_default(),
+        status,
+        timed_out,
+    })
+}
+
+#[cfg(unix)]
+fn rlimit(command: &mut Command, limits: &Limits) {
+    use std::os::unix::process::CommandExt;
+
+    let cpu = limits.cpu.as_secs().max(1) as libc::rlim_t;
+    let memory = limits.memory as libc::rlim_t;
+    let set = |resource, soft, hard| {
+        let limit = libc::rlimit {
+            rlim_cur: soft,
+            rlim_max: hard,
+        };
+        // SAFETY: setrlimit only reads `limit`, and is async-signal-safe.
+        match unsafe { libc::setrlimit(resource, &limit) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    };
+
+    // SAFETY: the closure only calls setrlimit, which is safe between fork
+    // and exec.
+    unsafe {
+        command.pre_exec(move || {
+            // SIGXCPU at the soft limit, SIGKILL a second later.
+            set(libc::RLIMIT_CPU, cpu, cpu + 1)?;
+            set(libc::RLIMIT_AS, memory, memory)
+        });
+    }
+}
+
+/// Why a child stopped without reporting every part.
+fn verdict(supervised: &Supervised, limits: &Limits) -> (Status, String) {
+    if supervised.timed_out {
+        return (
+            Status::Timeout,
+            format!("TIMEOUT: still running after {:?}", limits.timeout),
+        );
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        let signal = supervised.status.and_then(|status| status.signal());
+        if signal == Some(libc::SIGXCPU) || signal == Some(libc::SIGKILL) {
+            return (
+                Status::Timeout,
+                format!("TIMEOUT: used up {:?} of CPU time", limits.cpu),
+            );
+        }
+        // With too little address space even the stack can't grow.
+        if signal == Some(libc::SIGSEGV) && supervised.stderr.is_empty() {
+            return (
+                Status::Oom,
+                format!(
+                    "OOM: exceeded {} MiB (crashed with SIGSEGV)",
+                    limits.memory / (1024 * 1024)
+                ),
+            );
+        }
+    }
+
+    if supervised.stderr.contains("memory allocation of") {
+        return (
+            Status::Oom,
+            format!("OOM: exceeded {} MiB", limits.memory / (1024 * 1024)),
+        );
+    }
+
+    let status = match supervised.status {
+        Some(status) => status.to_string(),
+        None => "an unknown status".to_string(),
+    };
+    match supervised.stderr.lines().last() {
+        Some(last_line) => (
+            Status::Error,
+            format!("Sandboxed run exited with {}: {}", status, last_line),
+        ),
+        None => (
+            Status::Error,
+            format!("Sandboxed run exited with {}", status),
+        ),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{process::Command, time::Duration};
+
+    use super::{supervise, verdict, Limits, Supervised};
+    use crate::runner::Status;
+
+    fn sh(script: &str, limits: &Limits) -> Supervised {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        supervise(command, "input", limits).unwrap()
+    }
+
+    #[test]
+    fn passes_input_and_collects_lines() {
+        let supervised = sh("read line; echo \"$line\"; echo done", &Limits::default());
+        assert_eq!(supervised.lines, ["input", "done"]);
+        assert!(supervised.status.unwrap().success());
+    }
+
+    #[test]
+    fn kills_after_the_timeout() {
+        let limits = Limits {
+            timeout: Duration::from_millis(100),
+            ..Limits::default()
+        };
+        let supervised = sh("exec sleep 5", &limits);
+
+        assert!(supervised.timed_out);
+        assert_eq!(verdict(&supervised, &limits).0, Status::Timeout);
+    }
+
+    #[test]
+    fn caps_cpu_time() {
+        let limits = Limits {
+            cpu: Duration::from_secs(1),
+            ..Limits::default()
+        };
+        let supervised = sh("while :; do :; done", &limits);
+
+        assert!(!supervised.timed_out);
+        assert_eq!(verdict(&supervised, &limits).0, Status::Timeout);
+    }
+
+    #[test]
+    fn recognizes_failed_allocations() {
+        let limits = Limits::default();
+        let supervised = sh(
+            "echo 'memory allocation of 8589934592 bytes failed' >&2; kill -ABRT $$",
+            &limits,
+        );
+
+        assert_eq!(
+            verdict(&supervised, &limits),
+            (Status::Oom, "OOM: exceeded 1024 MiB".to_string())
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::ops::RangeInclusive;
+
+use crate::{Part, Result};
+
+/// Parses a day selector: either a single day (`3`) or an inclusive range
+/// (`1-5`, `1..5` or `1..=5`).
+pub fn parse_days(spec: &str) -> Result<RangeInclusive<u8>> {
+    let bounds = spec
+        .split_once("..=")
+        .or_else(|| spec.split_once(".."))
+        .or_else(|| spec.split_once('-'));
+
+    let (start, end) = match bounds {
+        Some((start, end)) => (start.trim().parse::<u8>()?, end.trim().parse::<u8>()?),
+        None => {
+            let day = spec.trim().parse::<u8>()?;
+            (day, day)
+        }
+    };
+
+    if start > end {
+        return Err(From::from(format!("Day range `{}` is backwards", spec)));
+    }
+
+    Ok(start..=end)
+}
+
+/// Parses a part selector; `None` selects both parts.
+pub fn parse_parts(spec: Option<&str>) -> Result<Vec<Part>> {
+    match spec {
+        None => Ok(vec![Part::One, Part::Two]),
+        Some("1") => Ok(vec![Part::One]),
+        Some("2") => Ok(vec![Part::Two]),
+        Some(other) => Err(From::from(format!("Part `{}` not recognized", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_days, parse_parts};
+    use crate::Part;
+
+    #[test]
+    fn single_days_and_ranges() {
+        assert_eq!(parse_days("3").unwrap(), 3..=3);
+        assert_eq!(parse_days("1-5").unwrap(), 1..=5);
+        assert_eq!(parse_days("2..4").unwrap(), 2..=4);
+        assert_eq!(parse_days("2..=4").unwrap(), 2..=4);
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn parts() {
+        assert_eq!(parse_parts(None).unwrap(), vec![Part::One, Part::Two]);
+        assert_eq!(parse_parts(Some("2")).unwrap(), vec![Part::Two]);
+        assert!(parse_parts(Some("3")).is_err());
+    }
+}
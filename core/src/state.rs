@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+/// The root of the checkout these binaries were built from, wherever
+/// they're run from.
+pub fn workspace() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+}
+
+/// Directory holding local, uncommitted state such as benchmark history.
+/// Defaults to `.aoc` at the workspace root; `AOC_STATE_DIR` overrides it.
+pub fn dir() -> PathBuf {
+    match std::env::var_os("AOC_STATE_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => workspace().join(".aoc"),
+    }
+}
+
+pub fn path(name: &str) -> PathBuf {
+    dir().join(name)
+}
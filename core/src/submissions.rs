@@ -0,0 +1,283 @@
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{state, Part, Result};
+
+const FILE_NAME: &str = "submissions.jsonl";
+
+/// How the site judged a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The answer wasn't looked at because the last one was too recent.
+    RateLimited {
+        #[serde(rename = "wait_s", with = "secs")]
+        wait: Duration,
+    },
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => f.write_str("correct"),
+            Outcome::TooHigh => f.write_str("too high"),
+            Outcome::TooLow => f.write_str("too low"),
+            Outcome::Wrong => f.write_str("wrong"),
+            Outcome::RateLimited { wait } => {
+                write!(f, "rate limited ({}s left)", wait.as_secs())
+            }
+        }
+    }
+}
+
+mod secs {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_secs(u64::deserialize(deserializer)?))
+    }
+}
+
+/// One answer sent to the site and what came back.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    /// When the site will next accept an answer for this part, if it said.
+    pub cooldown_until: Option<u64>,
+}
+
+impl Submission {
+    /// A submission made just now, cooling down for `wait` if given.
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        outcome: Outcome,
+        wait: Option<Duration>,
+    ) -> Self {
+        let timestamp = now();
+        Submission {
+            timestamp,
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            cooldown_until: wait.map(|wait| timestamp + wait.as_secs()),
+        }
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn record(submission: &Submission) -> Result<()> {
+    let path = state::path(FILE_NAME);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(submission)?)?;
+    Ok(())
+}
+
+/// Every recorded submission, oldest first.
+pub fn load() -> Result<Vec<Submission>> {
+    let path = state::path(FILE_NAME);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// How much longer the site will refuse answers for this part, as of `now`.
+pub fn cooldown(
+    submissions: &[Submission],
+    year: u16,
+    day: u8,
+    part: Part,
+    now: u64,
+) -> Option<Duration> {
+    submissions
+        .iter()
+        .filter(|submission| {
+            submission.year == year && submission.day == day && submission.part == part
+        })
+        .filter_map(|submission| submission.cooldown_until)
+        .max()
+        .filter(|&until| until > now)
+        .map(|until| Duration::from_secs(until - now))
+}
+
+/// Why `answer` for this part is already known to be wrong, if it is: it
+/// was rejected before, or it's no lower than an answer that was too high or
+/// no higher than one that was too low.
+pub fn flag(
+    submissions: &[Submission],
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Option<String> {
+    let rejected: Vec<&Submission> = submissions
+        .iter()
+        .filter(|submission| {
+            submission.year == year && submission.day == day && submission.part == part
+        })
+        .filter(|submission| {
+            matches!(
+                submission.outcome,
+                Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong
+            )
+        })
+        .collect();
+
+    if let Some(submission) = rejected
+        .iter()
+        .find(|submission| submission.answer == answer)
+    {
+        return Some(format!(
+            "{} was already rejected as {}",
+            answer, submission.outcome
+        ));
+    }
+
+    let answer: i128 = answer.trim().parse().ok()?;
+    let bound = |outcome: Outcome| {
+        rejected
+            .iter()
+            .filter(move |submission| submission.outcome == outcome)
+            .filter_map(|submission| submission.answer.trim().parse::<i128>().ok())
+    };
+
+    if let Some(low) = bound(Outcome::TooLow).filter(|&low| answer <= low).max() {
+        return Some(format!("{} is too low; {} already was", answer, low));
+    }
+    if let Some(high) = bound(Outcome::TooHigh).filter(|&high| answer >= high).min() {
+        return Some(format!("{} is too high; {} already was", answer, high));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{cooldown, flag, Outcome, Submission};
+    use crate::Part;
+
+    fn submission(part: Part, cooldown_until: Option<u64>) -> Submission {
+        Submission {
+            timestamp: 100,
+            year: 2022,
+            day: 1,
+            part,
+            answer: "1".to_string(),
+            outcome: Outcome::TooLow,
+            cooldown_until,
+        }
+    }
+
+    #[test]
+    fn cooldowns_are_per_part() {
+        let submissions = [
+            submission(Part::One, Some(160)),
+            submission(Part::Two, None),
+        ];
+
+        assert_eq!(
+            cooldown(&submissions, 2022, 1, Part::One, 130),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(cooldown(&submissions, 2022, 1, Part::One, 160), None);
+        assert_eq!(cooldown(&submissions, 2022, 1, Part::Two, 130), None);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let submission = Submission {
+            outcome: Outcome::RateLimited {
+                wait: Duration::from_secs(34),
+            },
+            ..submission(Part::Two, Some(134))
+        };
+        let json = serde_json::to_string(&submission).unwrap();
+        assert_eq!(
+            json,
+            r#"{"timestamp":100,"year":2022,"day":1,"part":2,"answer":"1","outcome":"rate_limited","wait_s":34,"cooldown_until":134}"#
+        );
+
+        let parsed: Submission = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.part, Part::Two);
+        assert_eq!(parsed.outcome, submission.outcome);
+    }
+
+    #[test]
+    fn flags_rejected_and_out_of_bounds_answers() {
+        let rejected = |answer: &str, outcome| Submission {
+            answer: answer.to_string(),
+            outcome,
+            ..submission(Part::One, None)
+        };
+        let submissions = [
+            rejected("100", Outcome::TooLow),
+            rejected("500", Outcome::TooHigh),
+            rejected("250", Outcome::Wrong),
+            rejected("ABC", Outcome::Wrong),
+        ];
+        let check = |answer| flag(&submissions, 2022, 1, Part::One, answer);
+
+        assert_eq!(
+            check("250").as_deref(),
+            Some("250 was already rejected as wrong")
+        );
+        assert_eq!(
+            check("ABC").as_deref(),
+            Some("ABC was already rejected as wrong")
+        );
+        assert_eq!(
+            check("99").as_deref(),
+            Some("99 is too low; 100 already was")
+        );
+        assert_eq!(
+            check("600").as_deref(),
+            Some("600 is too high; 500 already was")
+        );
+        assert_eq!(check("300"), None);
+        assert_eq!(check("XYZ"), None);
+        assert_eq!(flag(&submissions, 2022, 1, Part::Two, "99"), None);
+    }
+}
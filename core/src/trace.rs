@@ -0,0 +1,248 @@
+//! Lets a day show its working. Solutions emit events through [`info!`],
+//! [`debug!`] and [`trace!`]; the runner collects them per part, but only
+//! at or below the level asked for on the command line, so quiet runs pay
+//! for no formatting.
+//!
+//! ```ignore
+//! aoc_core::info!("{} × {} = {}", a, b, a * b);
+//! aoc_core::debug!("slope"; right = 3, down = 1, trees = 7);
+//! ```
+//!
+//! [`info!`]: crate::info
+//! [`debug!`]: crate::debug
+//! [`trace!`]: crate::trace
+
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use serde::{
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// How much detail an event goes into. Each level shows everything below it
+/// as well.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// How the answer came about, in a line or two: `--explain` or `-v`.
+    Info = 1,
+    /// The main steps along the way: `-vv`.
+    Debug = 2,
+    /// Everything, however noisy: `-vvv`.
+    Trace = 3,
+}
+
+impl Level {
+    /// The level for `-v` given `count` times; `None` for zero.
+    pub fn from_verbosity(count: u64) -> Option<Level> {
+        match count {
+            0 => None,
+            1 => Some(Level::Info),
+            2 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        }
+    }
+
+    /// The level the runners' `--explain` and `-v` flags ask for between
+    /// them.
+    pub fn from_flags(explain: bool, verbose: u64) -> Option<Level> {
+        Level::from_verbosity(verbose).max(explain.then_some(Level::Info))
+    }
+}
+
+/// One thing a solution had to say.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub level: Level,
+    pub message: String,
+    #[serde(
+        default,
+        serialize_with = "as_map",
+        deserialize_with = "from_map",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub fields: Vec<(String, String)>,
+}
+
+impl Display for Event {
+    /// The message then each field as `key=value`, indented two spaces a
+    /// level past `Info`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let indent = "  ".repeat(self.level as usize - 1);
+        write!(f, "{}{}", indent, self.message)?;
+        for (key, value) in &self.fields {
+            write!(f, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+fn as_map<S: Serializer>(fields: &[(String, String)], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(fields.len()))?;
+    for (key, value) in fields {
+        map.serialize_entry(key, value)?;
+    }
+    map.end()
+}
+
+fn from_map<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(String, String)>, D::Error> {
+    struct Fields;
+
+    impl<'de> Visitor<'de> for Fields {
+        type Value = Vec<(String, String)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a map of fields")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut fields = Vec::new();
+            while let Some(field) = map.next_entry()? {
+                fields.push(field);
+            }
+            Ok(fields)
+        }
+    }
+
+    deserializer.deserialize_map(Fields)
+}
+
+/// The most detailed level being collected, as a `Level` discriminant; 0
+/// when nothing is.
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    static EVENTS: RefCell<Option<Vec<Event>>> = const { RefCell::new(None) };
+}
+
+/// Sets how much detail [`capture`] collects from here on.
+pub fn set_level(level: Option<Level>) {
+    LEVEL.store(level.map_or(0, |level| level as u8), Ordering::Relaxed);
+}
+
+pub fn level() -> Option<Level> {
+    match LEVEL.load(Ordering::Relaxed) {
+        1 => Some(Level::Info),
+        2 => Some(Level::Debug),
+        3 => Some(Level::Trace),
+        _ => None,
+    }
+}
+
+/// Whether an event at `level` would be kept. The macros check this before
+/// formatting anything.
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Keeps `event` if a [`capture`] on this thread is collecting.
+pub fn emit(event: Event) {
+    EVENTS.with(|events| {
+        if let Some(events) = events.borrow_mut().as_mut() {
+            events.push(event);
+        }
+    });
+}
+
+/// Runs `f`, collecting whatever it emits at the current level.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<Event>) {
+    let outer = EVENTS.with(|events| events.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let events = EVENTS.with(|events| std::mem::replace(&mut *events.borrow_mut(), outer));
+    (result, events.unwrap_or_default())
+}
+
+/// Emits an event at `level`, formatting its message like `format!` and
+/// taking any `key = value` fields after a `;`.
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $fmt:literal $(, $arg:expr)* $(; $($key:ident = $value:expr),+)? $(,)?) => {
+        if $crate::trace::enabled($level) {
+            $crate::trace::emit($crate::trace::Event {
+                level: $level,
+                message: format!($fmt $(, $arg)*),
+                fields: vec![$($((stringify!($key).to_string(), $value.to_string())),+)?],
+            });
+        }
+    };
+}
+
+/// Emits an [`Info`](crate::trace::Level::Info) event.
+#[macro_export]
+macro_rules! info {
+    ($($event:tt)*) => { $crate::event!($crate::trace::Level::Info, $($event)*) };
+}
+
+/// Emits a [`Debug`](crate::trace::Level::Debug) event.
+#[macro_export]
+macro_rules! debug {
+    ($($event:tt)*) => { $crate::event!($crate::trace::Level::Debug, $($event)*) };
+}
+
+/// Emits a [`Trace`](crate::trace::Level::Trace) event.
+#[macro_export]
+macro_rules! trace {
+    ($($event:tt)*) => { $crate::event!($crate::trace::Level::Trace, $($event)*) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture, set_level, Event, Level};
+
+    fn noisy() -> u32 {
+        crate::info!("{} + {} = {}", 1, 2, 3);
+        crate::debug!("adding"; left = 1, right = 2);
+        crate::trace!("carry nothing");
+        3
+    }
+
+    // The level is process-wide, so everything that changes it is one test.
+    #[test]
+    fn collects_up_to_the_level_set() {
+        set_level(None);
+        assert_eq!(capture(noisy), (3, vec![]));
+
+        set_level(Some(Level::Debug));
+        let (_, events) = capture(noisy);
+        assert_eq!(
+            events,
+            vec![
+                Event {
+                    level: Level::Info,
+                    message: "1 + 2 = 3".to_string(),
+                    fields: vec![],
+                },
+                Event {
+                    level: Level::Debug,
+                    message: "adding".to_string(),
+                    fields: vec![
+                        ("left".to_string(), "1".to_string()),
+                        ("right".to_string(), "2".to_string())
+                    ],
+                },
+            ]
+        );
+        assert_eq!(events[1].to_string(), "  adding left=1 right=2");
+        let json = serde_json::to_string(&events[1]).unwrap();
+        assert_eq!(
+            json,
+            r#"{"level":"debug","message":"adding","fields":{"left":"1","right":"2"}}"#
+        );
+        assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), events[1]);
+
+        set_level(Some(Level::Trace));
+        assert_eq!(capture(noisy).1.len(), 3);
+        set_level(None);
+    }
+
+    #[test]
+    fn nothing_is_kept_outside_a_capture() {
+        crate::info!("dropped");
+        assert_eq!(capture(|| ()).1, vec![]);
+    }
+}
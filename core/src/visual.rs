@@ -0,0 +1,270 @@
+//! Lets a day draw what it's doing. Solutions hand [`frame`] a closure
+//! building a [`Frame`]; the closure only runs when the runner was asked to
+//! visualize, and the runner plays the frames back afterwards with [`play`].
+
+use std::{
+    cell::RefCell,
+    io::{self, BufRead, IsTerminal, Write},
+    ops::RangeInclusive,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// How wide [`View::Intervals`] draws its axis.
+const INTERVAL_WIDTH: usize = 60;
+
+/// One picture of a solution's state, with a line saying what it shows.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frame {
+    pub caption: String,
+    pub view: View,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum View {
+    /// Rows of characters, drawn as they are.
+    Grid(Vec<String>),
+    /// Stacks of crates, bottom first, drawn side by side and numbered from
+    /// 1.
+    Stacks(Vec<Vec<char>>),
+    /// Labelled ranges, drawn as bars along a shared axis.
+    Intervals(Vec<(String, RangeInclusive<i64>)>),
+}
+
+impl Frame {
+    pub fn render(&self) -> String {
+        let body = match &self.view {
+            View::Grid(rows) => rows.join("\n"),
+            View::Stacks(stacks) => stacks_picture(stacks),
+            View::Intervals(intervals) => interval_bars(intervals),
+        };
+        format!("{}\n\n{}\n", self.caption, body)
+    }
+}
+
+fn stacks_picture(stacks: &[Vec<char>]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut rows: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            let row = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(krate) => format!("[{}]", krate),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            row.trim_end().to_string()
+        })
+        .collect();
+    rows.push(
+        (1..=stacks.len())
+            .map(|number| format!("{:^3}", number))
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end()
+            .to_string(),
+    );
+    rows.join("\n")
+}
+
+fn interval_bars(intervals: &[(String, RangeInclusive<i64>)]) -> String {
+    let min = intervals.iter().map(|(_, range)| *range.start()).min();
+    let max = intervals.iter().map(|(_, range)| *range.end()).max();
+    let (min, max) = match (min, max) {
+        (Some(min), Some(max)) => (min, max.max(min)),
+        _ => return String::new(),
+    };
+    let label_width = intervals
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let span = (max - min) as f64 + 1.0;
+    let column = |value: i64| ((value - min) as f64 / span * INTERVAL_WIDTH as f64) as usize;
+    intervals
+        .iter()
+        .map(|(label, range)| {
+            let start = column(*range.start());
+            let end = column(*range.end()).max(start);
+            format!(
+                "{:>width$} |{}{}{}| {}-{}",
+                label,
+                " ".repeat(start),
+                "=".repeat(end - start + 1),
+                " ".repeat(INTERVAL_WIDTH.saturating_sub(end + 1)),
+                range.start(),
+                range.end(),
+                width = label_width
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static FRAMES: RefCell<Option<Vec<Frame>>> = const { RefCell::new(None) };
+}
+
+/// Sets whether [`capture`] collects frames from here on.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Adds the frame `draw` builds to the current [`capture`], if frames are
+/// being collected. Otherwise `draw` isn't called at all.
+pub fn frame(draw: impl FnOnce() -> Frame) {
+    if !enabled() {
+        return;
+    }
+    FRAMES.with(|frames| {
+        if let Some(frames) = frames.borrow_mut().as_mut() {
+            frames.push(draw());
+        }
+    });
+}
+
+/// Runs `f`, collecting whatever frames it draws.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<Frame>) {
+    let outer = FRAMES.with(|frames| frames.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let frames = FRAMES.with(|frames| std::mem::replace(&mut *frames.borrow_mut(), outer));
+    (result, frames.unwrap_or_default())
+}
+
+/// Where [`play`] draws.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Screen {
+    Stdout,
+    /// For when stdout is carrying JSON or TSV records.
+    Stderr,
+}
+
+/// How [`play`] moves from one frame to the next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Playback {
+    /// On its own, this many frames a second.
+    Fps(f64),
+    /// Whenever Enter is pressed; `q` then Enter stops.
+    Step,
+}
+
+/// Shows `frames` one after another on `screen`. On a terminal each frame
+/// replaces the last; otherwise they're printed in turn, without pauses.
+pub fn play(frames: &[Frame], playback: Playback, screen: Screen) {
+    let (mut out, terminal): (Box<dyn Write>, bool) = match screen {
+        Screen::Stdout => (Box::new(io::stdout()), io::stdout().is_terminal()),
+        Screen::Stderr => (Box::new(io::stderr()), io::stderr().is_terminal()),
+    };
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    for (index, frame) in frames.iter().enumerate() {
+        if terminal {
+            let _ = write!(out, "\x1b[2J\x1b[H");
+        }
+        let _ = write!(
+            out,
+            "{}frame {}/{}\n\n",
+            frame.render(),
+            index + 1,
+            frames.len()
+        );
+        let _ = out.flush();
+
+        if !terminal {
+            continue;
+        }
+        match playback {
+            Playback::Fps(fps) => thread::sleep(Duration::from_secs_f64(1.0 / fps.max(0.1))),
+            Playback::Step => {
+                let _ = write!(out, "Enter for the next frame, q to stop ");
+                let _ = out.flush();
+                match lines.next() {
+                    Some(Ok(line)) if line.trim() != "q" => {}
+                    _ => return,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture, frame, set_enabled, Frame, View};
+
+    fn render(view: View) -> String {
+        Frame {
+            caption: "caption".to_string(),
+            view,
+        }
+        .render()
+    }
+
+    #[test]
+    fn draws_grids_as_they_are() {
+        assert_eq!(
+            render(View::Grid(vec!["..#".to_string(), "#O.".to_string()])),
+            "caption\n\n..#\n#O.\n"
+        );
+    }
+
+    #[test]
+    fn draws_stacks_like_the_puzzle() {
+        assert_eq!(
+            render(View::Stacks(vec![
+                vec!['Z', 'N'],
+                vec!['M', 'C', 'D'],
+                vec!['P']
+            ])),
+            "caption\n\n    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n"
+        );
+    }
+
+    #[test]
+    fn draws_intervals_on_one_axis() {
+        let picture = render(View::Intervals(vec![
+            ("a".to_string(), 0..=29),
+            ("bb".to_string(), 30..=59),
+        ]));
+        let lines: Vec<&str> = picture.lines().skip(2).collect();
+
+        assert_eq!(
+            lines[0],
+            format!(" a |{}{}| 0-29", "=".repeat(30), " ".repeat(30))
+        );
+        assert_eq!(
+            lines[1],
+            format!("bb |{}{}| 30-59", " ".repeat(30), "=".repeat(30))
+        );
+    }
+
+    // Enabling is process-wide, so everything that changes it is one test.
+    #[test]
+    fn only_draws_while_enabled() {
+        let draw = || {
+            frame(|| Frame {
+                caption: "drawn".to_string(),
+                view: View::Grid(vec![]),
+            })
+        };
+
+        set_enabled(false);
+        assert!(capture(draw).1.is_empty());
+
+        set_enabled(true);
+        assert_eq!(capture(draw).1.len(), 1);
+        set_enabled(false);
+    }
+}